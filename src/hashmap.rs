@@ -1,30 +1,224 @@
+#[cfg(feature = "alloc-report")]
+use std::alloc::{GlobalAlloc, Layout, System};
+#[cfg(feature = "latency-report")]
+use std::sync::Barrier;
 use std::{
+    array,
     borrow::Borrow,
-    collections::HashMap as StdHashMap,
+    cell::{Cell, RefCell},
+    collections::{hash_map::Entry as StdEntry, HashMap as StdHashMap},
+    fmt,
     hash::{BuildHasher, Hash, RandomState},
     iter,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex, RwLock,
+    },
+    thread,
+    time::{Duration, Instant},
 };
 
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use dashmap::DashMap;
 use divan::black_box;
+use rand::{
+    rngs::SmallRng,
+    seq::{IndexedRandom, SliceRandom},
+    Rng, SeedableRng,
+};
+use rand_distr::{Distribution, Zipf};
 
 const THREADS: &[usize] = &[0, 1, 2, 4, 8, 16];
 const LENS: &[usize] = &[1, 2, 4, 8];
 
+/// get/insert/remove percentages for the mixed workload.
+const MIXES: &[Mix] = &[
+    Mix::new(95, 3, 2),
+    Mix::new(80, 10, 10),
+    Mix::new(50, 25, 25),
+];
+
+const KEY_DISTS: &[KeyDist] = &[
+    KeyDist::Uniform,
+    KeyDist::Zipf(0.99),
+    KeyDist::Zipf(1.2),
+    KeyDist::Hot,
+];
+
+/// Number of operations performed per iteration by batched benchmarks.
+const BATCH_SIZE: usize = 100;
+
+/// Number of keys drawn up front for randomized key streams; benchmarks cycle
+/// through them once exhausted.
+const NUM_SAMPLES: usize = 100_000;
+
+/// Numbers of sessions shared by all threads in the contended update
+/// benchmark.
+const HOT_SESSIONS: &[usize] = &[1, 4, 16];
+
+/// Session ID entropy, in bits, for the session creation benchmark. Small ID
+/// spaces make collisions likely enough to observe.
+const ID_BITS: &[u32] = &[16, 20, 128];
+
+/// Percentages of lookups that miss, e.g. for expired or forged session IDs.
+const MISS_RATIOS: &[MissRatio] = &[
+    MissRatio(0),
+    MissRatio(10),
+    MissRatio(50),
+    MissRatio(90),
+    MissRatio(100),
+];
+
+const SEED: u64 = 0x5e55_1011;
+
+/// Foreground get/insert percentages for the expiry workload.
+const EXPIRY_MIX: Mix = Mix::new(95, 5, 0);
+
+/// Background worker thread counts for the expiry sweep benchmark.
+const WORKERS: &[usize] = &[0, 1, 2, 4, 8];
+
+/// Number of sessions each user holds in the bulk logout benchmark.
+const SESSIONS_PER_USER: u64 = 10;
+
+const LOGOUTS: &[Logout] = &[Logout::Scan, Logout::Index];
+
+const SESSION_TTL: Duration = Duration::from_millis(50);
+
+/// Minimum time spent timing each expiry foreground benchmark, long enough
+/// for sessions to expire and be swept many times over.
+const EXPIRY_MIN_TIME: Duration = Duration::from_secs(1);
+
+/// Value sizes in bytes, spanning small cookies to large serialized sessions.
+const VALUE_SIZES: &[usize] = &[64, 1024, 16 * 1024, 64 * 1024];
+
+/// Number of distinct keys inserted per iteration by the growth benchmarks.
+const GROWTH_LENS: &[usize] = &[1_000, 10_000, 100_000];
+
 const NUM_KEYS: u64 = 5000;
 
-const NUM_KEYS_ERROR_MESSAGE: &str = "\
-    `NUM_KEYS` is not large enough to cover all iterations\n\
-    lower the iteration count with `sample_count` or `sample_size`, or increase `NUM_KEYS`\
-";
+/// Numbers of sessions in the populated map for the map-size sweeps, from
+/// well within the CPU cache to well beyond it.
+const MAP_SIZES: &[u64] = &[1_000, 10_000, 100_000, 1_000_000];
+
+/// Thread counts for the map-size sweeps. Unlike `THREADS`, these are all
+/// explicit, so the largest one bounds `MAP_SIZE_SAMPLE_SIZE`.
+const MAP_SIZE_THREADS: &[usize] = &[1, 2, 4, 8, 16];
+
+/// Number of samples taken by the map-size sweeps.
+const MAP_SIZE_SAMPLES: u32 = 100;
+
+/// Number of batches per sample of the map-size sweeps. Divan splits the
+/// samples between threads, so this is enough for each of the largest number
+/// of `MAP_SIZE_THREADS` to look up every session of the largest map at least
+/// once.
+const MAP_SIZE_SAMPLE_SIZE: u32 = {
+    let max_threads = MAP_SIZE_THREADS[MAP_SIZE_THREADS.len() - 1] as u64;
+    let max_size = MAP_SIZES[MAP_SIZES.len() - 1];
+    (max_threads * max_size / BATCH_SIZE as u64).div_ceil(MAP_SIZE_SAMPLES as u64) as u32
+};
+
+/// Numbers of sessions stored in each backend for the memory footprint
+/// report.
+#[cfg(feature = "alloc-report")]
+const FOOTPRINT_LENS: &[usize] = &[1_000, 100_000, 1_000_000];
+
+/// Thread counts for the latency report.
+#[cfg(feature = "latency-report")]
+const LATENCY_THREADS: &[usize] = &[1, 2, 4, 8, 16];
+
+/// Number of operations timed by each thread in the latency report.
+#[cfg(feature = "latency-report")]
+const LATENCY_OPS: usize = 100_000;
+
+/// Number of significant bits kept by `LatencyHistogram`, bounding the
+/// relative error of recorded latencies to 1 in 2^(`LATENCY_PRECISION` - 1).
+#[cfg(feature = "latency-report")]
+const LATENCY_PRECISION: u32 = 7;
 
 trait ConcurrentHashMap<K, V>: Default + Send + Sync {
+    fn with_capacity(capacity: usize) -> Self;
     fn insert(&self, key: K, val: V) -> Option<V>;
     fn get<Q>(&self, key: &Q) -> Option<V>
     where
         K: Borrow<Q>,
         Q: Hash + Eq + ?Sized;
+    fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized;
+    fn retain<F>(&self, f: F)
+    where
+        F: FnMut(&K, &V) -> bool;
+    fn read_with<Q, R, F>(&self, key: &Q, f: F) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        F: FnOnce(&V) -> R;
+    fn update<Q, R, F>(&self, key: &Q, f: F) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        F: FnOnce(&mut V) -> R;
+    /// Inserts `val` only if `key` is not yet present, returning whether it
+    /// was inserted.
+    fn insert_if_absent(&self, key: K, val: V) -> bool;
+    fn len(&self) -> usize;
+    /// Calls `f` on every entry, without any guarantee of a consistent
+    /// snapshot while the map is concurrently modified.
+    fn for_each<F>(&self, f: F)
+    where
+        F: FnMut(&K, &V);
+    /// A short name for report tables, e.g. `ShardedMutexHashMap<16>`.
+    fn name() -> String;
+}
+
+/// Exposes the key type of a map, so that benchmarks generic over the map can
+/// also be generic over how keys are represented.
+trait KeyedMap {
+    type Key: BenchKey;
+}
+
+/// Names the map of the same kind as `Self` that indexes sessions by user ID,
+/// so that benchmarks generic over the session map can build a matching
+/// index.
+trait UserIndexed {
+    type Index: ConcurrentHashMap<u64, Vec<String>>;
+}
+
+/// A key that benchmarks can generate deterministically.
+trait BenchKey: Clone + Eq + Hash + Send + Sync + 'static {
+    /// Returns the `n`th key of a benchmark's key set.
+    fn nth(n: u64) -> Self;
+}
+
+impl BenchKey for String {
+    fn nth(n: u64) -> Self {
+        n.to_string()
+    }
+}
+
+/// A random 128-bit session ID.
+impl BenchKey for u128 {
+    fn nth(n: u64) -> Self {
+        SmallRng::seed_from_u64(SEED ^ n).random()
+    }
+}
+
+impl BenchKey for [u8; 16] {
+    fn nth(n: u64) -> Self {
+        u128::nth(n).to_le_bytes()
+    }
+}
+
+/// A random 128-bit session ID, encoded as 22 characters of unpadded
+/// base64url, as it would appear in a cookie.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Base64SessionId(String);
+
+impl BenchKey for Base64SessionId {
+    fn nth(n: u64) -> Self {
+        Base64SessionId(URL_SAFE_NO_PAD.encode(<[u8; 16]>::nth(n)))
+    }
 }
 
 type MutexHashMap<K, V, S = RandomState> = Mutex<StdHashMap<K, V, S>>;
@@ -36,6 +230,10 @@ where
     V: Send,
     S: Send + Sync,
 {
+    fn with_capacity(capacity: usize) -> Self {
+        Mutex::new(StdHashMap::with_capacity_and_hasher(capacity, S::default()))
+    }
+
     fn insert(&self, key: K, val: V) -> Option<V> {
         self.lock().unwrap().insert(key, val)
     }
@@ -47,17 +245,89 @@ where
     {
         self.lock().unwrap().get(key).cloned()
     }
+
+    fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.lock().unwrap().remove(key)
+    }
+
+    fn retain<F>(&self, mut f: F)
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self.lock().unwrap().retain(|k, v| f(k, v));
+    }
+
+    fn read_with<Q, R, F>(&self, key: &Q, f: F) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        F: FnOnce(&V) -> R,
+    {
+        self.lock().unwrap().get(key).map(f)
+    }
+
+    fn update<Q, R, F>(&self, key: &Q, f: F) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        F: FnOnce(&mut V) -> R,
+    {
+        self.lock().unwrap().get_mut(key).map(f)
+    }
+
+    fn insert_if_absent(&self, key: K, val: V) -> bool {
+        match self.lock().unwrap().entry(key) {
+            StdEntry::Occupied(_) => false,
+            StdEntry::Vacant(entry) => {
+                entry.insert(val);
+                true
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.lock().unwrap().len()
+    }
+
+    fn for_each<F>(&self, mut f: F)
+    where
+        F: FnMut(&K, &V),
+    {
+        self.lock().unwrap().iter().for_each(|(k, v)| f(k, v));
+    }
+
+    fn name() -> String {
+        "MutexHashMap".to_owned()
+    }
 }
 
-impl<K: Eq + Hash, V: Clone, S: BuildHasher + Clone + Default> ConcurrentHashMap<K, V>
-    for DashMap<K, V, S>
+impl<K: BenchKey, V, S> KeyedMap for MutexHashMap<K, V, S> {
+    type Key = K;
+}
+
+impl UserIndexed for MutexHashMap<String, UserSession> {
+    type Index = MutexHashMap<u64, Vec<String>>;
+}
+
+type RwLockHashMap<K, V, S = RandomState> = RwLock<StdHashMap<K, V, S>>;
+
+impl<K: Eq + Hash, V: Clone, S: BuildHasher + Default> ConcurrentHashMap<K, V>
+    for RwLockHashMap<K, V, S>
 where
     K: Send + Sync,
     V: Send + Sync,
     S: Send + Sync,
 {
+    fn with_capacity(capacity: usize) -> Self {
+        RwLock::new(StdHashMap::with_capacity_and_hasher(capacity, S::default()))
+    }
+
     fn insert(&self, key: K, val: V) -> Option<V> {
-        self.insert(key, val)
+        self.write().unwrap().insert(key, val)
     }
 
     fn get<Q>(&self, key: &Q) -> Option<V>
@@ -65,19 +335,119 @@ where
         K: Borrow<Q>,
         Q: Hash + Eq + ?Sized,
     {
-        self.get(key).as_deref().cloned()
+        self.read().unwrap().get(key).cloned()
+    }
+
+    fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.write().unwrap().remove(key)
+    }
+
+    fn retain<F>(&self, mut f: F)
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self.write().unwrap().retain(|k, v| f(k, v));
+    }
+
+    fn read_with<Q, R, F>(&self, key: &Q, f: F) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        F: FnOnce(&V) -> R,
+    {
+        self.read().unwrap().get(key).map(f)
+    }
+
+    fn update<Q, R, F>(&self, key: &Q, f: F) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        F: FnOnce(&mut V) -> R,
+    {
+        self.write().unwrap().get_mut(key).map(f)
+    }
+
+    fn insert_if_absent(&self, key: K, val: V) -> bool {
+        match self.write().unwrap().entry(key) {
+            StdEntry::Occupied(_) => false,
+            StdEntry::Vacant(entry) => {
+                entry.insert(val);
+                true
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.read().unwrap().len()
+    }
+
+    fn for_each<F>(&self, mut f: F)
+    where
+        F: FnMut(&K, &V),
+    {
+        self.read().unwrap().iter().for_each(|(k, v)| f(k, v));
+    }
+
+    fn name() -> String {
+        "RwLockHashMap".to_owned()
     }
 }
 
-impl<K: Eq + Hash, V: Clone, H: BuildHasher + Default> ConcurrentHashMap<K, V>
-    for scc::HashMap<K, V, H>
+impl<K: BenchKey, V, S> KeyedMap for RwLockHashMap<K, V, S> {
+    type Key = K;
+}
+
+impl UserIndexed for RwLockHashMap<String, UserSession> {
+    type Index = RwLockHashMap<u64, Vec<String>>;
+}
+
+/// `N` mutex-guarded maps, with each key assigned to a shard by its hash.
+struct ShardedMutexHashMap<K, V, S, const N: usize> {
+    shards: [MutexHashMap<K, V, S>; N],
+    hasher: S,
+}
+
+impl<K, V, S: BuildHasher, const N: usize> ShardedMutexHashMap<K, V, S, N> {
+    fn shard<Q>(&self, key: &Q) -> &MutexHashMap<K, V, S>
+    where
+        Q: Hash + ?Sized,
+    {
+        let hash = self.hasher.hash_one(key) as usize;
+        &self.shards[hash % N]
+    }
+}
+
+impl<K, V, S: Default, const N: usize> Default for ShardedMutexHashMap<K, V, S, N> {
+    fn default() -> Self {
+        assert!(N > 0, "`ShardedMutexHashMap` requires at least one shard");
+        ShardedMutexHashMap {
+            shards: array::from_fn(|_| Mutex::new(StdHashMap::default())),
+            hasher: S::default(),
+        }
+    }
+}
+
+impl<K: Eq + Hash, V: Clone, S: BuildHasher + Default, const N: usize> ConcurrentHashMap<K, V>
+    for ShardedMutexHashMap<K, V, S, N>
 where
-    K: Send + Sync,
-    V: Send + Sync,
-    H: Send + Sync,
+    K: Send,
+    V: Send,
+    S: Send + Sync,
 {
+    fn with_capacity(capacity: usize) -> Self {
+        let map = Self::default();
+        for shard in &map.shards {
+            shard.lock().unwrap().reserve(capacity.div_ceil(N));
+        }
+        map
+    }
+
     fn insert(&self, key: K, val: V) -> Option<V> {
-        self.upsert(key, val)
+        self.shard(&key).lock().unwrap().insert(key, val)
     }
 
     fn get<Q>(&self, key: &Q) -> Option<V>
@@ -85,87 +455,2407 @@ where
         K: Borrow<Q>,
         Q: Hash + Eq + ?Sized,
     {
-        self.get(key).as_deref().cloned()
+        self.shard(key).lock().unwrap().get(key).cloned()
     }
-}
 
-pub fn main() {
-    divan::main();
-}
+    fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.shard(key).lock().unwrap().remove(key)
+    }
 
-#[divan::bench(
-    types = [MutexHashMap<String, String>, DashMap<String, String>, scc::HashMap<String, String>],
-    args = LENS,
-    threads = THREADS
-)]
-fn insert<H: ConcurrentHashMap<String, String>>(bencher: divan::Bencher, len: usize) {
-    let map = Arc::new(H::default());
+    fn retain<F>(&self, mut f: F)
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        for shard in &self.shards {
+            shard.lock().unwrap().retain(|k, v| f(k, v));
+        }
+    }
 
-    let keys = iter::repeat_with(|| "hello".to_owned());
-    let values = iter::repeat_with(|| "world".to_owned());
+    fn read_with<Q, R, F>(&self, key: &Q, f: F) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        F: FnOnce(&V) -> R,
+    {
+        self.shard(key).lock().unwrap().get(key).map(f)
+    }
 
-    bencher
-        .counter(len)
-        .with_inputs(|| (keys, values))
-        .bench_values(|(mut key, mut value)| {
-            let map = Arc::clone(&map);
-            for _ in 0..len {
-                map.insert(key.next().unwrap(), value.next().unwrap());
+    fn update<Q, R, F>(&self, key: &Q, f: F) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        F: FnOnce(&mut V) -> R,
+    {
+        self.shard(key).lock().unwrap().get_mut(key).map(f)
+    }
+
+    fn insert_if_absent(&self, key: K, val: V) -> bool {
+        match self.shard(&key).lock().unwrap().entry(key) {
+            StdEntry::Occupied(_) => false,
+            StdEntry::Vacant(entry) => {
+                entry.insert(val);
+                true
             }
-        });
-}
+        }
+    }
 
-#[divan::bench(
-    types = [MutexHashMap<String, String>, DashMap<String, String>, scc::HashMap<String, String>],
-    args = LENS,
-    threads = THREADS
-)]
-fn get<H: ConcurrentHashMap<String, String>>(bencher: divan::Bencher, len: usize) {
-    let map = Arc::new(H::default());
+    fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.lock().unwrap().len())
+            .sum()
+    }
 
-    let keys = populate_map(map.as_ref(), || "world".to_owned());
-    let keys_iter = MutexIter::new(keys.into_iter());
+    fn for_each<F>(&self, mut f: F)
+    where
+        F: FnMut(&K, &V),
+    {
+        for shard in &self.shards {
+            shard.lock().unwrap().iter().for_each(|(k, v)| f(k, v));
+        }
+    }
 
-    bencher
-        .counter(len)
-        .with_inputs(|| keys_iter.next().expect(NUM_KEYS_ERROR_MESSAGE))
-        .bench_values(|key| {
-            let map = Arc::clone(&map);
-            for _ in 0..len {
-                let val = map.get(&key);
-                black_box(val);
-            }
-        });
+    fn name() -> String {
+        format!("ShardedMutexHashMap<{N}>")
+    }
+}
+
+impl<K: BenchKey, V, S, const N: usize> KeyedMap for ShardedMutexHashMap<K, V, S, N> {
+    type Key = K;
+}
+
+impl<const N: usize> UserIndexed for ShardedMutexHashMap<String, UserSession, RandomState, N> {
+    type Index = ShardedMutexHashMap<u64, Vec<String>, RandomState, N>;
 }
 
-fn populate_map<F>(map: &impl ConcurrentHashMap<String, String>, f: F) -> Vec<String>
+impl<K: Eq + Hash, V: Clone, S: BuildHasher + Clone + Default> ConcurrentHashMap<K, V>
+    for DashMap<K, V, S>
 where
-    F: Fn() -> String,
+    K: Send + Sync,
+    V: Send + Sync,
+    S: Send + Sync,
 {
-    let keys = (1..=NUM_KEYS).map(|n| n.to_string()).collect::<Vec<_>>();
+    fn with_capacity(capacity: usize) -> Self {
+        DashMap::with_capacity_and_hasher(capacity, S::default())
+    }
 
-    for key in &keys {
-        map.insert(key.clone(), f());
+    fn insert(&self, key: K, val: V) -> Option<V> {
+        self.insert(key, val)
     }
 
-    keys
-}
+    fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get(key).as_deref().cloned()
+    }
 
-struct MutexIter<I> {
-    iter: Mutex<I>,
-}
+    fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.remove(key).map(|(_, v)| v)
+    }
 
-impl<I, T> MutexIter<I>
-where
-    I: Iterator<Item = T>,
-{
-    fn new(iter: I) -> MutexIter<I> {
-        let iter = Mutex::new(iter);
-        MutexIter { iter }
+    fn retain<F>(&self, mut f: F)
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self.retain(|k, v| f(k, v));
     }
 
-    #[track_caller]
-    fn next(&self) -> Option<T> {
-        self.iter.lock().unwrap().next()
+    fn read_with<Q, R, F>(&self, key: &Q, f: F) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        F: FnOnce(&V) -> R,
+    {
+        self.get(key).map(|entry| f(entry.value()))
+    }
+
+    fn update<Q, R, F>(&self, key: &Q, f: F) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        F: FnOnce(&mut V) -> R,
+    {
+        self.get_mut(key).map(|mut entry| f(entry.value_mut()))
+    }
+
+    fn insert_if_absent(&self, key: K, val: V) -> bool {
+        match self.entry(key) {
+            dashmap::Entry::Occupied(_) => false,
+            dashmap::Entry::Vacant(entry) => {
+                entry.insert(val);
+                true
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn for_each<F>(&self, mut f: F)
+    where
+        F: FnMut(&K, &V),
+    {
+        self.iter().for_each(|entry| f(entry.key(), entry.value()));
+    }
+
+    fn name() -> String {
+        "DashMap".to_owned()
+    }
+}
+
+impl<K: BenchKey, V, S> KeyedMap for DashMap<K, V, S> {
+    type Key = K;
+}
+
+impl UserIndexed for DashMap<String, UserSession> {
+    type Index = DashMap<u64, Vec<String>>;
+}
+
+impl<K: Eq + Hash, V: Clone, H: BuildHasher + Default> ConcurrentHashMap<K, V>
+    for scc::HashMap<K, V, H>
+where
+    K: Send + Sync,
+    V: Send + Sync,
+    H: Send + Sync,
+{
+    fn with_capacity(capacity: usize) -> Self {
+        scc::HashMap::with_capacity_and_hasher(capacity, H::default())
+    }
+
+    fn insert(&self, key: K, val: V) -> Option<V> {
+        self.upsert(key, val)
+    }
+
+    fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get(key).as_deref().cloned()
+    }
+
+    fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.remove(key).map(|(_, v)| v)
+    }
+
+    fn retain<F>(&self, mut f: F)
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self.retain(|k, v| f(k, v));
+    }
+
+    fn read_with<Q, R, F>(&self, key: &Q, f: F) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        F: FnOnce(&V) -> R,
+    {
+        self.read(key, |_, v| f(v))
+    }
+
+    fn update<Q, R, F>(&self, key: &Q, f: F) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        F: FnOnce(&mut V) -> R,
+    {
+        self.update(key, |_, v| f(v))
+    }
+
+    fn insert_if_absent(&self, key: K, val: V) -> bool {
+        self.insert(key, val).is_ok()
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn for_each<F>(&self, f: F)
+    where
+        F: FnMut(&K, &V),
+    {
+        self.scan(f);
+    }
+
+    fn name() -> String {
+        "scc::HashMap".to_owned()
+    }
+}
+
+impl<K: BenchKey, V, H: BuildHasher> KeyedMap for scc::HashMap<K, V, H> {
+    type Key = K;
+}
+
+impl UserIndexed for scc::HashMap<String, UserSession> {
+    type Index = scc::HashMap<u64, Vec<String>>;
+}
+
+impl<K: Clone + Eq + Hash, V: Clone, H: BuildHasher + Default> ConcurrentHashMap<K, V>
+    for scc::HashIndex<K, V, H>
+where
+    K: Send + Sync + 'static,
+    V: Send + Sync + 'static,
+    H: Send + Sync,
+{
+    fn with_capacity(capacity: usize) -> Self {
+        scc::HashIndex::with_capacity_and_hasher(capacity, H::default())
+    }
+
+    fn insert(&self, key: K, val: V) -> Option<V> {
+        match self.entry(key) {
+            scc::hash_index::Entry::Occupied(entry) => {
+                let prev = entry.get().clone();
+                entry.update(val);
+                Some(prev)
+            }
+            scc::hash_index::Entry::Vacant(entry) => {
+                entry.insert_entry(val);
+                None
+            }
+        }
+    }
+
+    fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.peek_with(key, |_, v| v.clone())
+    }
+
+    fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get(key).map(|entry| {
+            let prev = entry.get().clone();
+            entry.remove_entry();
+            prev
+        })
+    }
+
+    fn retain<F>(&self, f: F)
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self.retain(f);
+    }
+
+    fn read_with<Q, R, F>(&self, key: &Q, f: F) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        F: FnOnce(&V) -> R,
+    {
+        self.peek_with(key, |_, v| f(v))
+    }
+
+    fn update<Q, R, F>(&self, key: &Q, f: F) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        F: FnOnce(&mut V) -> R,
+    {
+        // Entries are immutable once inserted, so the value is replaced with an
+        // updated copy while the entry is locked.
+        self.get(key).map(|entry| {
+            let mut val = entry.get().clone();
+            let ret = f(&mut val);
+            entry.update(val);
+            ret
+        })
+    }
+
+    fn insert_if_absent(&self, key: K, val: V) -> bool {
+        self.insert(key, val).is_ok()
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn for_each<F>(&self, mut f: F)
+    where
+        F: FnMut(&K, &V),
+    {
+        let guard = scc::ebr::Guard::new();
+        self.iter(&guard).for_each(|(k, v)| f(k, v));
+    }
+
+    fn name() -> String {
+        "scc::HashIndex".to_owned()
+    }
+}
+
+impl<K: BenchKey, V, H: BuildHasher> KeyedMap for scc::HashIndex<K, V, H> {
+    type Key = K;
+}
+
+/// Maximum capacity of a `BoundedHashCache`, large enough that no benchmark
+/// populates it to the point of eviction.
+const CACHE_MAXIMUM_CAPACITY: usize = 1 << 21;
+
+/// An `scc::HashCache` whose maximum capacity is `CACHE_MAXIMUM_CAPACITY`
+/// rather than scc's default of 256 entries.
+struct BoundedHashCache<K, V, H: BuildHasher = RandomState>(scc::HashCache<K, V, H>);
+
+impl<K: Eq + Hash, V, H: BuildHasher + Default> Default for BoundedHashCache<K, V, H> {
+    fn default() -> Self {
+        BoundedHashCache(scc::HashCache::with_capacity_and_hasher(
+            0,
+            CACHE_MAXIMUM_CAPACITY,
+            H::default(),
+        ))
+    }
+}
+
+impl<K: Eq + Hash, V: Clone, H: BuildHasher + Default> ConcurrentHashMap<K, V>
+    for BoundedHashCache<K, V, H>
+where
+    K: Send + Sync,
+    V: Send + Sync,
+    H: Send + Sync,
+{
+    fn with_capacity(capacity: usize) -> Self {
+        BoundedHashCache(scc::HashCache::with_capacity_and_hasher(
+            capacity,
+            CACHE_MAXIMUM_CAPACITY.max(capacity),
+            H::default(),
+        ))
+    }
+
+    fn insert(&self, key: K, val: V) -> Option<V> {
+        match self.0.entry(key) {
+            scc::hash_cache::Entry::Occupied(mut entry) => Some(entry.put(val)),
+            scc::hash_cache::Entry::Vacant(entry) => {
+                entry.put_entry(val);
+                None
+            }
+        }
+    }
+
+    fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.0.get(key).map(|entry| entry.get().clone())
+    }
+
+    fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.0.remove(key).map(|(_, v)| v)
+    }
+
+    fn retain<F>(&self, mut f: F)
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self.0.retain(|k, v| f(k, v));
+    }
+
+    fn read_with<Q, R, F>(&self, key: &Q, f: F) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        F: FnOnce(&V) -> R,
+    {
+        self.0.read(key, |_, v| f(v))
+    }
+
+    fn update<Q, R, F>(&self, key: &Q, f: F) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        F: FnOnce(&mut V) -> R,
+    {
+        self.0.get(key).map(|mut entry| f(entry.get_mut()))
+    }
+
+    fn insert_if_absent(&self, key: K, val: V) -> bool {
+        self.0.put(key, val).is_ok()
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn for_each<F>(&self, f: F)
+    where
+        F: FnMut(&K, &V),
+    {
+        self.0.scan(f);
+    }
+
+    fn name() -> String {
+        "BoundedHashCache".to_owned()
+    }
+}
+
+impl<K: BenchKey, V, H: BuildHasher> KeyedMap for BoundedHashCache<K, V, H> {
+    type Key = K;
+}
+
+pub fn main() {
+    // Every allocation goes through the counting allocator, so benchmarks
+    // timed alongside the footprint report would not be comparable to those
+    // of a normal run.
+    #[cfg(feature = "alloc-report")]
+    print_footprint_report();
+    if cfg!(feature = "alloc-report") {
+        return;
+    }
+
+    divan::main();
+    print_collision_report();
+    print_expiry_report();
+    print_writer_report();
+    #[cfg(feature = "latency-report")]
+    print_latency_report();
+}
+
+#[divan::bench(
+    types = [
+        MutexHashMap<String, String>,
+        RwLockHashMap<String, String>,
+        ShardedMutexHashMap<String, String, RandomState, 4>,
+        ShardedMutexHashMap<String, String, RandomState, 16>,
+        ShardedMutexHashMap<String, String, RandomState, 64>,
+        DashMap<String, String>,
+        scc::HashMap<String, String>,
+        scc::HashIndex<String, String>,
+        BoundedHashCache<String, String>,
+    ],
+    consts = VALUE_SIZES,
+    args = LENS,
+    threads = THREADS
+)]
+fn insert<H: ConcurrentHashMap<String, String>, const SIZE: usize>(
+    bencher: divan::Bencher,
+    len: usize,
+) {
+    bench_insert::<H, _, _>(bencher, len, "hello".to_owned(), sized_value(SIZE));
+}
+
+#[divan::bench(
+    types = [
+        MutexHashMap<String, String>,
+        RwLockHashMap<String, String>,
+        ShardedMutexHashMap<String, String, RandomState, 4>,
+        ShardedMutexHashMap<String, String, RandomState, 16>,
+        ShardedMutexHashMap<String, String, RandomState, 64>,
+        DashMap<String, String>,
+        scc::HashMap<String, String>,
+        scc::HashIndex<String, String>,
+        BoundedHashCache<String, String>,
+    ],
+    consts = VALUE_SIZES,
+    args = LENS,
+    threads = THREADS
+)]
+fn get<H: ConcurrentHashMap<String, String>, const SIZE: usize>(
+    bencher: divan::Bencher,
+    len: usize,
+) {
+    bench_get::<H, String, _>(bencher, len, sized_value(SIZE));
+}
+
+#[divan::bench(
+    types = [
+        MutexHashMap<String, String>,
+        MutexHashMap<u128, String>,
+        MutexHashMap<[u8; 16], String>,
+        MutexHashMap<Base64SessionId, String>,
+        RwLockHashMap<String, String>,
+        RwLockHashMap<u128, String>,
+        RwLockHashMap<[u8; 16], String>,
+        RwLockHashMap<Base64SessionId, String>,
+        ShardedMutexHashMap<String, String, RandomState, 4>,
+        ShardedMutexHashMap<u128, String, RandomState, 4>,
+        ShardedMutexHashMap<[u8; 16], String, RandomState, 4>,
+        ShardedMutexHashMap<Base64SessionId, String, RandomState, 4>,
+        ShardedMutexHashMap<String, String, RandomState, 16>,
+        ShardedMutexHashMap<u128, String, RandomState, 16>,
+        ShardedMutexHashMap<[u8; 16], String, RandomState, 16>,
+        ShardedMutexHashMap<Base64SessionId, String, RandomState, 16>,
+        ShardedMutexHashMap<String, String, RandomState, 64>,
+        ShardedMutexHashMap<u128, String, RandomState, 64>,
+        ShardedMutexHashMap<[u8; 16], String, RandomState, 64>,
+        ShardedMutexHashMap<Base64SessionId, String, RandomState, 64>,
+        DashMap<String, String>,
+        DashMap<u128, String>,
+        DashMap<[u8; 16], String>,
+        DashMap<Base64SessionId, String>,
+        scc::HashMap<String, String>,
+        scc::HashMap<u128, String>,
+        scc::HashMap<[u8; 16], String>,
+        scc::HashMap<Base64SessionId, String>,
+        scc::HashIndex<String, String>,
+        scc::HashIndex<u128, String>,
+        scc::HashIndex<[u8; 16], String>,
+        scc::HashIndex<Base64SessionId, String>,
+        BoundedHashCache<String, String>,
+        BoundedHashCache<u128, String>,
+        BoundedHashCache<[u8; 16], String>,
+        BoundedHashCache<Base64SessionId, String>,
+    ],
+    args = LENS,
+    threads = THREADS
+)]
+fn insert_session_id<H>(bencher: divan::Bencher, len: usize)
+where
+    H: KeyedMap + ConcurrentHashMap<H::Key, String>,
+{
+    bench_insert::<H, _, _>(bencher, len, H::Key::nth(0), "world".to_owned());
+}
+
+#[divan::bench(
+    types = [
+        MutexHashMap<String, String>,
+        MutexHashMap<u128, String>,
+        MutexHashMap<[u8; 16], String>,
+        MutexHashMap<Base64SessionId, String>,
+        RwLockHashMap<String, String>,
+        RwLockHashMap<u128, String>,
+        RwLockHashMap<[u8; 16], String>,
+        RwLockHashMap<Base64SessionId, String>,
+        ShardedMutexHashMap<String, String, RandomState, 4>,
+        ShardedMutexHashMap<u128, String, RandomState, 4>,
+        ShardedMutexHashMap<[u8; 16], String, RandomState, 4>,
+        ShardedMutexHashMap<Base64SessionId, String, RandomState, 4>,
+        ShardedMutexHashMap<String, String, RandomState, 16>,
+        ShardedMutexHashMap<u128, String, RandomState, 16>,
+        ShardedMutexHashMap<[u8; 16], String, RandomState, 16>,
+        ShardedMutexHashMap<Base64SessionId, String, RandomState, 16>,
+        ShardedMutexHashMap<String, String, RandomState, 64>,
+        ShardedMutexHashMap<u128, String, RandomState, 64>,
+        ShardedMutexHashMap<[u8; 16], String, RandomState, 64>,
+        ShardedMutexHashMap<Base64SessionId, String, RandomState, 64>,
+        DashMap<String, String>,
+        DashMap<u128, String>,
+        DashMap<[u8; 16], String>,
+        DashMap<Base64SessionId, String>,
+        scc::HashMap<String, String>,
+        scc::HashMap<u128, String>,
+        scc::HashMap<[u8; 16], String>,
+        scc::HashMap<Base64SessionId, String>,
+        scc::HashIndex<String, String>,
+        scc::HashIndex<u128, String>,
+        scc::HashIndex<[u8; 16], String>,
+        scc::HashIndex<Base64SessionId, String>,
+        BoundedHashCache<String, String>,
+        BoundedHashCache<u128, String>,
+        BoundedHashCache<[u8; 16], String>,
+        BoundedHashCache<Base64SessionId, String>,
+    ],
+    args = LENS,
+    threads = THREADS
+)]
+fn get_session_id<H>(bencher: divan::Bencher, len: usize)
+where
+    H: KeyedMap + ConcurrentHashMap<H::Key, String>,
+{
+    bench_get::<H, H::Key, _>(bencher, len, "world".to_owned());
+}
+
+fn bench_insert<H, K, V>(bencher: divan::Bencher, len: usize, key: K, value: V)
+where
+    H: ConcurrentHashMap<K, V>,
+    K: Clone + Sync,
+    V: Clone + Sync,
+{
+    let map = Arc::new(H::default());
+
+    let keys = iter::repeat_with(|| key.clone());
+    let values = iter::repeat_with(|| value.clone());
+
+    bencher
+        .counter(len)
+        .with_inputs(|| (keys, values))
+        .bench_values(|(mut key, mut value)| {
+            let map = Arc::clone(&map);
+            for _ in 0..len {
+                map.insert(key.next().unwrap(), value.next().unwrap());
+            }
+        });
+}
+
+fn bench_get<H, K, V>(bencher: divan::Bencher, len: usize, value: V)
+where
+    H: ConcurrentHashMap<K, V>,
+    K: BenchKey,
+    V: Clone,
+{
+    let map = Arc::new(H::default());
+
+    let keys = populate_map(map.as_ref(), || value.clone());
+    let key_stream = KeyStream::new(keys);
+
+    bencher
+        .counter(len)
+        .with_inputs(|| key_stream.next())
+        .bench_values(|key| {
+            let map = Arc::clone(&map);
+            for _ in 0..len {
+                let val = map.get(&key);
+                black_box(val);
+            }
+        });
+}
+
+/// Inserts `len` distinct keys into a fresh, empty map, so that every
+/// iteration pays for growing the table.
+///
+/// Each iteration builds its own map, so this benchmark is single-threaded.
+#[divan::bench(
+    types = [
+        MutexHashMap<String, String>,
+        RwLockHashMap<String, String>,
+        ShardedMutexHashMap<String, String, RandomState, 4>,
+        ShardedMutexHashMap<String, String, RandomState, 16>,
+        ShardedMutexHashMap<String, String, RandomState, 64>,
+        DashMap<String, String>,
+        scc::HashMap<String, String>,
+        scc::HashIndex<String, String>,
+        BoundedHashCache<String, String>,
+    ],
+    args = GROWTH_LENS
+)]
+fn insert_distinct<H: ConcurrentHashMap<String, String>>(bencher: divan::Bencher, len: usize) {
+    bench_insert_distinct(bencher, len, H::default);
+}
+
+/// Like `insert_distinct`, but the map is created with enough capacity for
+/// every key up front.
+#[divan::bench(
+    types = [
+        MutexHashMap<String, String>,
+        RwLockHashMap<String, String>,
+        ShardedMutexHashMap<String, String, RandomState, 4>,
+        ShardedMutexHashMap<String, String, RandomState, 16>,
+        ShardedMutexHashMap<String, String, RandomState, 64>,
+        DashMap<String, String>,
+        scc::HashMap<String, String>,
+        scc::HashIndex<String, String>,
+        BoundedHashCache<String, String>,
+    ],
+    args = GROWTH_LENS
+)]
+fn insert_distinct_presized<H: ConcurrentHashMap<String, String>>(
+    bencher: divan::Bencher,
+    len: usize,
+) {
+    bench_insert_distinct(bencher, len, || H::with_capacity(len));
+}
+
+fn bench_insert_distinct<H, F>(bencher: divan::Bencher, len: usize, new_map: F)
+where
+    H: ConcurrentHashMap<String, String>,
+    F: Fn() -> H + Sync,
+{
+    let keys = (1..=len as u64).map(String::nth).collect::<Vec<_>>();
+
+    bencher
+        .counter(len)
+        .with_inputs(|| (new_map(), keys.clone()))
+        .bench_values(|(map, keys)| {
+            for key in keys {
+                map.insert(key, "world".to_owned());
+            }
+            // Returned so that the map is dropped outside of the timed section.
+            map
+        });
+}
+
+/// Like `insert_distinct`, but every thread inserts batches of fresh keys
+/// into one shared map, so that growing the table contends with concurrent
+/// inserts.
+///
+/// Once `len` keys have been handed out for a map, it is replaced by a fresh,
+/// empty one.
+#[divan::bench(
+    types = [
+        MutexHashMap<String, String>,
+        RwLockHashMap<String, String>,
+        ShardedMutexHashMap<String, String, RandomState, 4>,
+        ShardedMutexHashMap<String, String, RandomState, 16>,
+        ShardedMutexHashMap<String, String, RandomState, 64>,
+        DashMap<String, String>,
+        scc::HashMap<String, String>,
+        scc::HashIndex<String, String>,
+        BoundedHashCache<String, String>,
+    ],
+    args = GROWTH_LENS,
+    threads = THREADS
+)]
+fn insert_distinct_shared<H: ConcurrentHashMap<String, String>>(
+    bencher: divan::Bencher,
+    len: usize,
+) {
+    // The current map along with the number of keys handed out for it so
+    // far. Keys are numbered across all maps, so none is ever inserted twice.
+    let state = Mutex::new((Arc::new(H::default()), 0));
+    let next_key = AtomicU64::new(1);
+
+    bencher
+        .counter(BATCH_SIZE)
+        .with_inputs(|| {
+            let mut state = state.lock().unwrap();
+            let (map, handed_out) = &mut *state;
+            if *handed_out >= len {
+                *map = Arc::new(H::default());
+                *handed_out = 0;
+            }
+            *handed_out += BATCH_SIZE;
+
+            let first = next_key.fetch_add(BATCH_SIZE as u64, Ordering::Relaxed);
+            let keys = (first..first + BATCH_SIZE as u64)
+                .map(String::nth)
+                .collect::<Vec<_>>();
+            (Arc::clone(map), keys)
+        })
+        .bench_values(|(map, keys)| {
+            for key in keys {
+                map.insert(key, "world".to_owned());
+            }
+            // Returned so that a replaced map is dropped outside of the timed
+            // section.
+            map
+        });
+}
+
+/// Like `get`, but reads the value in place instead of cloning it.
+#[divan::bench(
+    types = [
+        MutexHashMap<String, String>,
+        RwLockHashMap<String, String>,
+        ShardedMutexHashMap<String, String, RandomState, 4>,
+        ShardedMutexHashMap<String, String, RandomState, 16>,
+        ShardedMutexHashMap<String, String, RandomState, 64>,
+        DashMap<String, String>,
+        scc::HashMap<String, String>,
+        scc::HashIndex<String, String>,
+        BoundedHashCache<String, String>,
+    ],
+    consts = VALUE_SIZES,
+    args = LENS,
+    threads = THREADS
+)]
+fn read_with<H: ConcurrentHashMap<String, String>, const SIZE: usize>(
+    bencher: divan::Bencher,
+    len: usize,
+) {
+    let map = Arc::new(H::default());
+
+    let value = sized_value(SIZE);
+    let keys = populate_map(map.as_ref(), || value.clone());
+    let key_stream = KeyStream::new(keys);
+
+    bencher
+        .counter(len)
+        .with_inputs(|| key_stream.next())
+        .bench_values(|key| {
+            let map = Arc::clone(&map);
+            for _ in 0..len {
+                let val_len = map.read_with(&key, |val| black_box(val).len());
+                black_box(val_len);
+            }
+        });
+}
+
+#[divan::bench(
+    types = [
+        MutexHashMap<String, String>,
+        RwLockHashMap<String, String>,
+        ShardedMutexHashMap<String, String, RandomState, 4>,
+        ShardedMutexHashMap<String, String, RandomState, 16>,
+        ShardedMutexHashMap<String, String, RandomState, 64>,
+        DashMap<String, String>,
+        scc::HashMap<String, String>,
+    ],
+    args = LENS,
+    threads = THREADS
+)]
+fn remove<H: ConcurrentHashMap<String, String>>(bencher: divan::Bencher, len: usize) {
+    let map = Arc::new(H::default());
+
+    let keys = populate_map(map.as_ref(), || "world".to_owned());
+    let key_stream = KeyStream::new(keys);
+
+    bencher
+        .counter(len)
+        .with_inputs(|| {
+            let keys = iter::repeat_with(|| key_stream.next())
+                .take(len)
+                .collect::<Vec<_>>();
+            // Removed keys must be present again before they are timed.
+            for key in &keys {
+                map.insert(key.clone(), "world".to_owned());
+            }
+            keys
+        })
+        .bench_values(|keys| {
+            let map = Arc::clone(&map);
+            for key in &keys {
+                let val = map.remove(key);
+                black_box(val);
+            }
+        });
+}
+
+#[divan::bench(
+    types = [
+        MutexHashMap<String, String>,
+        RwLockHashMap<String, String>,
+        ShardedMutexHashMap<String, String, RandomState, 4>,
+        ShardedMutexHashMap<String, String, RandomState, 16>,
+        ShardedMutexHashMap<String, String, RandomState, 64>,
+        DashMap<String, String>,
+        scc::HashMap<String, String>,
+    ],
+    args = MIXES,
+    threads = THREADS
+)]
+fn mixed<H: ConcurrentHashMap<String, String>>(bencher: divan::Bencher, mix: Mix) {
+    let map = Arc::new(H::default());
+
+    let keys = populate_map(map.as_ref(), || "world".to_owned());
+    let key_stream = KeyStream::new(keys);
+
+    bench_mixed(bencher, &map, mix, &key_stream, || "world".to_owned());
+}
+
+/// Read-modify-write updates with every thread contending on the same
+/// `hot` sessions.
+#[divan::bench(
+    types = [
+        MutexHashMap<String, String>,
+        RwLockHashMap<String, String>,
+        ShardedMutexHashMap<String, String, RandomState, 4>,
+        ShardedMutexHashMap<String, String, RandomState, 16>,
+        ShardedMutexHashMap<String, String, RandomState, 64>,
+        DashMap<String, String>,
+        scc::HashMap<String, String>,
+        scc::HashIndex<String, String>,
+        BoundedHashCache<String, String>,
+    ],
+    args = HOT_SESSIONS,
+    threads = THREADS
+)]
+fn update<H: ConcurrentHashMap<String, String>>(bencher: divan::Bencher, hot: usize) {
+    let map = Arc::new(H::default());
+
+    let keys = populate_map(map.as_ref(), || "world".to_owned());
+    let key_stream = KeyStream::new(KeyDist::Uniform.sample(&keys[..hot]));
+
+    bencher
+        .counter(BATCH_SIZE)
+        .with_inputs(|| next_batch(&key_stream))
+        .bench_values(|keys| {
+            let map = Arc::clone(&map);
+            for key in &keys {
+                let updated = map.update(key, |val| {
+                    val.clear();
+                    val.push_str(black_box("updated"));
+                });
+                black_box(updated);
+            }
+        });
+}
+
+/// Like `get`, but over a map populated with `size` sessions, every one of
+/// which is looked up in turn.
+#[divan::bench(
+    types = [
+        MutexHashMap<String, String>,
+        RwLockHashMap<String, String>,
+        ShardedMutexHashMap<String, String, RandomState, 4>,
+        ShardedMutexHashMap<String, String, RandomState, 16>,
+        ShardedMutexHashMap<String, String, RandomState, 64>,
+        DashMap<String, String>,
+        scc::HashMap<String, String>,
+        scc::HashIndex<String, String>,
+        BoundedHashCache<String, String>,
+    ],
+    args = MAP_SIZES,
+    threads = MAP_SIZE_THREADS,
+    sample_count = MAP_SIZE_SAMPLES,
+    sample_size = MAP_SIZE_SAMPLE_SIZE
+)]
+fn get_map_size<H: ConcurrentHashMap<String, String>>(bencher: divan::Bencher, size: u64) {
+    let map = Arc::new(H::default());
+
+    let keys = populate_map_len(map.as_ref(), size, || "world".to_owned());
+    let key_stream = KeyStream::new(keys);
+
+    bencher
+        .counter(BATCH_SIZE)
+        .with_inputs(|| next_batch(&key_stream))
+        .bench_values(|keys| {
+            let map = Arc::clone(&map);
+            for key in &keys {
+                let val = map.get(key);
+                black_box(val);
+            }
+        });
+}
+
+/// Like `update`, but spread over every session of a map populated with
+/// `size` sessions rather than a few hot ones.
+#[divan::bench(
+    types = [
+        MutexHashMap<String, String>,
+        RwLockHashMap<String, String>,
+        ShardedMutexHashMap<String, String, RandomState, 4>,
+        ShardedMutexHashMap<String, String, RandomState, 16>,
+        ShardedMutexHashMap<String, String, RandomState, 64>,
+        DashMap<String, String>,
+        scc::HashMap<String, String>,
+        scc::HashIndex<String, String>,
+        BoundedHashCache<String, String>,
+    ],
+    args = MAP_SIZES,
+    threads = MAP_SIZE_THREADS,
+    sample_count = MAP_SIZE_SAMPLES,
+    sample_size = MAP_SIZE_SAMPLE_SIZE
+)]
+fn update_map_size<H: ConcurrentHashMap<String, String>>(bencher: divan::Bencher, size: u64) {
+    let map = Arc::new(H::default());
+
+    let keys = populate_map_len(map.as_ref(), size, || "world".to_owned());
+    let key_stream = KeyStream::new(keys);
+
+    bencher
+        .counter(BATCH_SIZE)
+        .with_inputs(|| next_batch(&key_stream))
+        .bench_values(|keys| {
+            let map = Arc::clone(&map);
+            for key in &keys {
+                let updated = map.update(key, |val| {
+                    val.clear();
+                    val.push_str(black_box("updated"));
+                });
+                black_box(updated);
+            }
+        });
+}
+
+/// Runs the read-heavy mix (the first entry of `MIXES`) over a map populated
+/// with `size` sessions.
+#[divan::bench(
+    types = [
+        MutexHashMap<String, String>,
+        RwLockHashMap<String, String>,
+        ShardedMutexHashMap<String, String, RandomState, 4>,
+        ShardedMutexHashMap<String, String, RandomState, 16>,
+        ShardedMutexHashMap<String, String, RandomState, 64>,
+        DashMap<String, String>,
+        scc::HashMap<String, String>,
+    ],
+    args = MAP_SIZES,
+    threads = MAP_SIZE_THREADS,
+    sample_count = MAP_SIZE_SAMPLES,
+    sample_size = MAP_SIZE_SAMPLE_SIZE
+)]
+fn mixed_map_size<H: ConcurrentHashMap<String, String>>(bencher: divan::Bencher, size: u64) {
+    let map = Arc::new(H::default());
+
+    let keys = populate_map_len(map.as_ref(), size, || "world".to_owned());
+    let key_stream = KeyStream::new(keys);
+
+    bench_mixed(bencher, &map, MIXES[0], &key_stream, || "world".to_owned());
+}
+
+/// Concurrent session creation with random IDs of `bits` bits of entropy,
+/// inserted only if no session with the same ID exists.
+///
+/// The map holds `NUM_KEYS` sessions with IDs of the same entropy throughout:
+/// every created session is removed again right away, as if it had expired,
+/// so that the chance of a collision doesn't depend on how long the
+/// benchmark runs. Collisions are counted and printed once all benchmarks
+/// have run.
+#[divan::bench(
+    types = [
+        MutexHashMap<u128, String>,
+        RwLockHashMap<u128, String>,
+        ShardedMutexHashMap<u128, String, RandomState, 4>,
+        ShardedMutexHashMap<u128, String, RandomState, 16>,
+        ShardedMutexHashMap<u128, String, RandomState, 64>,
+        DashMap<u128, String>,
+        scc::HashMap<u128, String>,
+        scc::HashIndex<u128, String>,
+        BoundedHashCache<u128, String>,
+    ],
+    args = ID_BITS,
+    threads = THREADS
+)]
+fn create_session<H: ConcurrentHashMap<u128, String>>(bencher: divan::Bencher, bits: u32) {
+    let map = Arc::new(H::default());
+
+    let mask = u128::MAX.checked_shr(128 - bits).unwrap_or(0);
+    let mut rng = SmallRng::seed_from_u64(SEED);
+    while map.len() < NUM_KEYS as usize {
+        map.insert_if_absent(rng.random::<u128>() & mask, "world".to_owned());
+    }
+
+    let attempts = AtomicU64::new(0);
+    let collisions = AtomicU64::new(0);
+    // The ID generator of each thread, seeded from `SEED` plus one more than
+    // the thread's index so that no thread repeats the populated IDs.
+    let rngs = Mutex::new(StdHashMap::new());
+
+    bencher
+        .counter(BATCH_SIZE)
+        .with_inputs(|| {
+            attempts.fetch_add(BATCH_SIZE as u64, Ordering::Relaxed);
+            let mut rngs = rngs.lock().unwrap();
+            let index = rngs.len() as u64;
+            let rng = rngs
+                .entry(thread::current().id())
+                .or_insert_with(|| SmallRng::seed_from_u64(SEED + 1 + index));
+            iter::repeat_with(|| rng.random::<u128>() & mask)
+                .take(BATCH_SIZE)
+                .collect::<Vec<_>>()
+        })
+        .bench_values(|ids| {
+            let map = Arc::clone(&map);
+            for id in ids {
+                if map.insert_if_absent(id, "world".to_owned()) {
+                    map.remove(&id);
+                } else {
+                    collisions.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        });
+
+    COLLISION_REPORT.lock().unwrap().push(CollisionRow {
+        map: H::name(),
+        bits,
+        threads: rngs.into_inner().unwrap().len(),
+        attempts: attempts.into_inner(),
+        collisions: collisions.into_inner(),
+    });
+}
+
+/// Lookups where a share of the keys are absent from the map.
+#[divan::bench(
+    types = [
+        MutexHashMap<String, String>,
+        RwLockHashMap<String, String>,
+        ShardedMutexHashMap<String, String, RandomState, 4>,
+        ShardedMutexHashMap<String, String, RandomState, 16>,
+        ShardedMutexHashMap<String, String, RandomState, 64>,
+        DashMap<String, String>,
+        scc::HashMap<String, String>,
+        scc::HashIndex<String, String>,
+        BoundedHashCache<String, String>,
+    ],
+    args = MISS_RATIOS,
+    threads = THREADS
+)]
+fn get_miss<H: ConcurrentHashMap<String, String>>(bencher: divan::Bencher, ratio: MissRatio) {
+    let map = Arc::new(H::default());
+
+    let keys = populate_map(map.as_ref(), || "world".to_owned());
+    let key_stream = KeyStream::new(ratio.sample(&keys));
+
+    bencher
+        .counter(BATCH_SIZE)
+        .with_inputs(|| next_batch(&key_stream))
+        .bench_values(|keys| {
+            let map = Arc::clone(&map);
+            for key in &keys {
+                let val = map.get(key);
+                black_box(val);
+            }
+        });
+}
+
+#[divan::bench(
+    types = [
+        MutexHashMap<String, String>,
+        RwLockHashMap<String, String>,
+        ShardedMutexHashMap<String, String, RandomState, 4>,
+        ShardedMutexHashMap<String, String, RandomState, 16>,
+        ShardedMutexHashMap<String, String, RandomState, 64>,
+        DashMap<String, String>,
+        scc::HashMap<String, String>,
+    ],
+    args = KEY_DISTS,
+    threads = THREADS
+)]
+fn get_skewed<H: ConcurrentHashMap<String, String>>(bencher: divan::Bencher, dist: KeyDist) {
+    let map = Arc::new(H::default());
+
+    let keys = populate_map(map.as_ref(), || "world".to_owned());
+    let key_stream = KeyStream::new(dist.sample(&keys));
+
+    bencher
+        .counter(BATCH_SIZE)
+        .with_inputs(|| next_batch(&key_stream))
+        .bench_values(|keys| {
+            let map = Arc::clone(&map);
+            for key in &keys {
+                let val = map.get(key);
+                black_box(val);
+            }
+        });
+}
+
+#[divan::bench(
+    types = [
+        MutexHashMap<String, String>,
+        RwLockHashMap<String, String>,
+        ShardedMutexHashMap<String, String, RandomState, 4>,
+        ShardedMutexHashMap<String, String, RandomState, 16>,
+        ShardedMutexHashMap<String, String, RandomState, 64>,
+        DashMap<String, String>,
+        scc::HashMap<String, String>,
+    ],
+    args = KEY_DISTS,
+    threads = THREADS
+)]
+fn insert_skewed<H: ConcurrentHashMap<String, String>>(bencher: divan::Bencher, dist: KeyDist) {
+    let map = Arc::new(H::default());
+
+    let keys = populate_map(map.as_ref(), || "world".to_owned());
+    let key_stream = KeyStream::new(dist.sample(&keys));
+
+    bencher
+        .counter(BATCH_SIZE)
+        .with_inputs(|| next_batch(&key_stream))
+        .bench_values(|keys| {
+            let map = Arc::clone(&map);
+            for key in keys {
+                let val = map.insert(key, "world".to_owned());
+                black_box(val);
+            }
+        });
+}
+
+/// Runs the read-heavy mix (the first entry of `MIXES`) with keys drawn from
+/// `dist`.
+#[divan::bench(
+    types = [
+        MutexHashMap<String, String>,
+        RwLockHashMap<String, String>,
+        ShardedMutexHashMap<String, String, RandomState, 4>,
+        ShardedMutexHashMap<String, String, RandomState, 16>,
+        ShardedMutexHashMap<String, String, RandomState, 64>,
+        DashMap<String, String>,
+        scc::HashMap<String, String>,
+    ],
+    args = KEY_DISTS,
+    threads = THREADS
+)]
+fn mixed_skewed<H: ConcurrentHashMap<String, String>>(bencher: divan::Bencher, dist: KeyDist) {
+    let map = Arc::new(H::default());
+
+    let keys = populate_map(map.as_ref(), || "world".to_owned());
+    let key_stream = KeyStream::new(dist.sample(&keys));
+
+    bench_mixed(bencher, &map, MIXES[0], &key_stream, || "world".to_owned());
+}
+
+/// Foreground get/insert throughput while a background thread sweeps expired
+/// sessions back-to-back.
+///
+/// Swept sessions are inserted again with a fresh expiry, so that the map
+/// keeps holding `NUM_KEYS` sessions however fast each backend is. The number
+/// of sweeps that overlapped foreground work is printed once all benchmarks
+/// have run.
+#[divan::bench(
+    types = [
+        MutexHashMap<String, Session>,
+        RwLockHashMap<String, Session>,
+        ShardedMutexHashMap<String, Session, RandomState, 4>,
+        ShardedMutexHashMap<String, Session, RandomState, 16>,
+        ShardedMutexHashMap<String, Session, RandomState, 64>,
+        DashMap<String, Session>,
+        scc::HashMap<String, Session>,
+    ],
+    threads = THREADS,
+    min_time = EXPIRY_MIN_TIME
+)]
+fn expiry_foreground<H: ConcurrentHashMap<String, Session>>(bencher: divan::Bencher) {
+    let map = Arc::new(H::default());
+
+    let keys = populate_sessions(map.as_ref(), SESSION_TTL);
+    let key_stream = KeyStream::new(keys);
+    let done = AtomicBool::new(false);
+    // Foreground inserts only happen within timed batches, so a change in
+    // their count during a sweep shows that the two overlapped.
+    let inserts = AtomicU64::new(0);
+
+    let sweeper = thread::scope(|s| {
+        let sweeper = s.spawn(|| {
+            let mut stats = SweepStats::default();
+            while !done.load(Ordering::Relaxed) {
+                let inserts_before = inserts.load(Ordering::Relaxed);
+                let now = Instant::now();
+                let mut expired = Vec::new();
+                map.retain(|key, session| {
+                    let live = session.expires_at > now;
+                    if !live {
+                        expired.push(key.clone());
+                    }
+                    live
+                });
+                let overlapped = inserts.load(Ordering::Relaxed) != inserts_before;
+
+                stats.sweeps += 1;
+                stats.overlapped += overlapped as u64;
+                stats.swept += expired.len() as u64;
+                for key in expired {
+                    map.insert(key, Session::new(SESSION_TTL));
+                }
+            }
+            stats
+        });
+
+        bench_mixed(bencher, &map, EXPIRY_MIX, &key_stream, || {
+            inserts.fetch_add(1, Ordering::Relaxed);
+            Session::new(SESSION_TTL)
+        });
+        done.store(true, Ordering::Relaxed);
+        sweeper.join().unwrap()
+    });
+
+    EXPIRY_REPORT.lock().unwrap().push(ExpiryRow {
+        map: H::name(),
+        threads: key_stream.readers(),
+        stats: sweeper,
+    });
+}
+
+/// Duration of a single expiry sweep over the populated map while `workers`
+/// background threads keep serving gets and inserts.
+#[divan::bench(
+    types = [
+        MutexHashMap<String, Session>,
+        RwLockHashMap<String, Session>,
+        ShardedMutexHashMap<String, Session, RandomState, 4>,
+        ShardedMutexHashMap<String, Session, RandomState, 16>,
+        ShardedMutexHashMap<String, Session, RandomState, 64>,
+        DashMap<String, Session>,
+        scc::HashMap<String, Session>,
+    ],
+    args = WORKERS,
+    // Every input of a sample is generated before any is timed, so only the
+    // first sweep of a sample would find the expired sessions.
+    sample_size = 1
+)]
+fn expiry_sweep<H: ConcurrentHashMap<String, Session>>(bencher: divan::Bencher, workers: usize) {
+    let map = Arc::new(H::default());
+
+    // Live sessions must outlast the benchmark so that only the entries
+    // expired during setup are swept.
+    let keys = populate_sessions(map.as_ref(), Duration::from_secs(3600));
+    let (expired, live) = keys.split_at(keys.len() / 10);
+    let done = AtomicBool::new(false);
+
+    thread::scope(|s| {
+        for worker in 0..workers {
+            let map = map.as_ref();
+            let done = &done;
+            s.spawn(move || {
+                let ops = EXPIRY_MIX.ops();
+                let keys = live.iter().cycle().skip(worker * live.len() / workers);
+                for (op, key) in ops.iter().cycle().zip(keys) {
+                    if done.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    match op {
+                        Op::Get => {
+                            black_box(map.get(key));
+                        }
+                        Op::Insert => {
+                            black_box(
+                                map.insert(key.clone(), Session::new(Duration::from_secs(3600))),
+                            );
+                        }
+                        Op::Remove => {
+                            black_box(map.remove(key));
+                        }
+                    }
+                }
+            });
+        }
+
+        bencher
+            .counter(keys.len())
+            .with_inputs(|| {
+                for key in expired {
+                    map.insert(key.clone(), Session::new(Duration::ZERO));
+                }
+            })
+            .bench_values(|()| sweep_expired(map.as_ref()));
+        done.store(true, Ordering::Relaxed);
+    });
+}
+
+/// Revokes every session of one user, either by scanning all sessions or
+/// through a user-ID index.
+#[divan::bench(
+    types = [
+        MutexHashMap<String, UserSession>,
+        RwLockHashMap<String, UserSession>,
+        ShardedMutexHashMap<String, UserSession, RandomState, 4>,
+        ShardedMutexHashMap<String, UserSession, RandomState, 16>,
+        ShardedMutexHashMap<String, UserSession, RandomState, 64>,
+        DashMap<String, UserSession>,
+        scc::HashMap<String, UserSession>,
+    ],
+    args = LOGOUTS,
+    threads = THREADS
+)]
+fn logout_user<H>(bencher: divan::Bencher, logout: Logout)
+where
+    H: UserIndexed + ConcurrentHashMap<String, UserSession>,
+{
+    let store = Arc::new(UserSessionStore::<H>::default());
+
+    let users = (1..=NUM_KEYS / SESSIONS_PER_USER).collect::<Vec<_>>();
+    for &user_id in &users {
+        store.create_all(user_id);
+    }
+    let user_stream = KeyStream::new(users);
+
+    bencher
+        .counter(SESSIONS_PER_USER as usize)
+        .with_inputs(|| {
+            // Revoked sessions must exist again before they are timed.
+            let user_id = user_stream.next();
+            match logout {
+                // The index is left alone so that it doesn't grow.
+                Logout::Scan => {
+                    for id in user_session_ids(user_id) {
+                        store.sessions.insert(id, UserSession::new(user_id));
+                    }
+                }
+                // Sessions of users that weren't drawn since setup are still
+                // indexed, so they are revoked first to not index them twice.
+                Logout::Index => {
+                    store.logout(user_id);
+                    store.create_all(user_id);
+                }
+            }
+            user_id
+        })
+        .bench_values(|user_id| {
+            let store = Arc::clone(&store);
+            match logout {
+                Logout::Scan => store.logout_by_scan(user_id),
+                Logout::Index => {
+                    black_box(store.logout(user_id));
+                }
+            }
+        });
+}
+
+fn sweep_expired(map: &impl ConcurrentHashMap<String, Session>) {
+    let now = Instant::now();
+    map.retain(|_, session| session.expires_at > now);
+}
+
+/// Duration of a full-table scan, e.g. to list active sessions, while
+/// `workers` background threads keep overwriting sessions.
+///
+/// The writers' throughput during and between scans is printed once all
+/// benchmarks have run.
+#[divan::bench(
+    types = [
+        MutexHashMap<String, String>,
+        RwLockHashMap<String, String>,
+        ShardedMutexHashMap<String, String, RandomState, 4>,
+        ShardedMutexHashMap<String, String, RandomState, 16>,
+        ShardedMutexHashMap<String, String, RandomState, 64>,
+        DashMap<String, String>,
+        scc::HashMap<String, String>,
+        scc::HashIndex<String, String>,
+        BoundedHashCache<String, String>,
+    ],
+    args = WORKERS
+)]
+fn scan<H: ConcurrentHashMap<String, String>>(bencher: divan::Bencher, workers: usize) {
+    // Every session is visited once per scan.
+    let bencher = bencher.counter(NUM_KEYS as usize);
+    bench_under_writes::<H>(bencher, "scan", workers, |map| {
+        map.for_each(|k, v| {
+            black_box((k, v));
+        });
+    });
+}
+
+/// Like `scan`, but counts the sessions instead, e.g. for metrics.
+#[divan::bench(
+    types = [
+        MutexHashMap<String, String>,
+        RwLockHashMap<String, String>,
+        ShardedMutexHashMap<String, String, RandomState, 4>,
+        ShardedMutexHashMap<String, String, RandomState, 16>,
+        ShardedMutexHashMap<String, String, RandomState, 64>,
+        DashMap<String, String>,
+        scc::HashMap<String, String>,
+        scc::HashIndex<String, String>,
+        BoundedHashCache<String, String>,
+    ],
+    args = WORKERS
+)]
+fn len<H: ConcurrentHashMap<String, String>>(bencher: divan::Bencher, workers: usize) {
+    bench_under_writes::<H>(bencher, "len", workers, |map| {
+        black_box(map.len());
+    });
+}
+
+/// Times `f` on a populated map while `workers` background threads keep
+/// overwriting its sessions, each of which times its own writes depending on
+/// whether `f` is running.
+fn bench_under_writes<H: ConcurrentHashMap<String, String>>(
+    bencher: divan::Bencher,
+    name: &'static str,
+    workers: usize,
+    f: impl Fn(&H) + Sync,
+) {
+    let map = H::default();
+
+    let keys = populate_map(&map, || "world".to_owned());
+    let scanning = AtomicBool::new(false);
+    let done = AtomicBool::new(false);
+    let stats = Mutex::new(WriteStats::default());
+
+    thread::scope(|s| {
+        for worker in 0..workers {
+            let (map, keys, stats) = (&map, &keys, &stats);
+            let (scanning, done) = (&scanning, &done);
+            s.spawn(move || {
+                let mut local = WriteStats::default();
+                let keys = keys.iter().cycle().skip(worker * keys.len() / workers);
+                for key in keys {
+                    if done.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let during_scan = scanning.load(Ordering::Relaxed);
+                    let val = "world".to_owned();
+                    let start = Instant::now();
+                    black_box(map.insert(key.clone(), val));
+                    local.record(during_scan, start.elapsed());
+                }
+                stats.lock().unwrap().merge(&local);
+            });
+        }
+
+        bencher.bench(|| {
+            scanning.store(true, Ordering::Relaxed);
+            f(&map);
+            scanning.store(false, Ordering::Relaxed);
+        });
+        done.store(true, Ordering::Relaxed);
+    });
+
+    if workers > 0 {
+        WRITER_REPORT.lock().unwrap().push(WriterRow {
+            bench: name,
+            map: H::name(),
+            workers,
+            stats: stats.into_inner().unwrap(),
+        });
+    }
+}
+
+fn bench_mixed<H, V, F>(
+    bencher: divan::Bencher,
+    map: &Arc<H>,
+    mix: Mix,
+    key_stream: &KeyStream<String>,
+    f: F,
+) where
+    H: ConcurrentHashMap<String, V>,
+    F: Fn() -> V + Sync,
+{
+    let ops = mix.ops();
+
+    bencher
+        .counter(ops.len())
+        .with_inputs(|| (reshuffled(&ops), next_batch(key_stream)))
+        .bench_values(|(ops, keys)| {
+            let map = Arc::clone(map);
+            for (op, key) in ops.iter().zip(keys) {
+                match op {
+                    Op::Get => {
+                        black_box(map.get(&key));
+                    }
+                    Op::Insert => {
+                        black_box(map.insert(key, f()));
+                    }
+                    Op::Remove => {
+                        black_box(map.remove(&key));
+                    }
+                }
+            }
+        });
+}
+
+fn next_batch<T: Clone>(key_stream: &KeyStream<T>) -> Vec<T> {
+    iter::repeat_with(|| key_stream.next())
+        .take(BATCH_SIZE)
+        .collect()
+}
+
+fn populate_map<K, V, F>(map: &impl ConcurrentHashMap<K, V>, f: F) -> Vec<K>
+where
+    K: BenchKey,
+    F: Fn() -> V,
+{
+    populate_map_len(map, NUM_KEYS, f)
+}
+
+fn populate_map_len<K, V, F>(map: &impl ConcurrentHashMap<K, V>, len: u64, f: F) -> Vec<K>
+where
+    K: BenchKey,
+    F: Fn() -> V,
+{
+    let keys = (1..=len).map(K::nth).collect::<Vec<_>>();
+
+    for key in &keys {
+        map.insert(key.clone(), f());
+    }
+
+    keys
+}
+
+/// Collision counts recorded by `create_session`.
+static COLLISION_REPORT: Mutex<Vec<CollisionRow>> = Mutex::new(Vec::new());
+
+struct CollisionRow {
+    map: String,
+    bits: u32,
+    threads: usize,
+    attempts: u64,
+    collisions: u64,
+}
+
+fn print_collision_report() {
+    let rows = COLLISION_REPORT.lock().unwrap();
+    if rows.is_empty() {
+        return;
+    }
+
+    let width = rows.iter().map(|row| row.map.len()).max().unwrap();
+    println!();
+    println!("create_session collisions");
+    println!(
+        "{:width$}  {:>4}  {:>7}  {:>10}  {:>10}  {:>8}",
+        "map", "bits", "threads", "attempts", "collisions", "rate"
+    );
+    for row in rows.iter() {
+        let rate = row.collisions as f64 / row.attempts as f64;
+        println!(
+            "{:width$}  {:>4}  {:>7}  {:>10}  {:>10}  {:>7.3}%",
+            row.map,
+            row.bits,
+            row.threads,
+            row.attempts,
+            row.collisions,
+            rate * 100.0,
+        );
+    }
+}
+
+/// Sweep counts recorded by `expiry_foreground`.
+static EXPIRY_REPORT: Mutex<Vec<ExpiryRow>> = Mutex::new(Vec::new());
+
+struct ExpiryRow {
+    map: String,
+    threads: usize,
+    stats: SweepStats,
+}
+
+#[derive(Default)]
+struct SweepStats {
+    sweeps: u64,
+    /// Sweeps during which foreground batches were timed.
+    overlapped: u64,
+    /// Sessions removed, and inserted again, by all sweeps.
+    swept: u64,
+}
+
+fn print_expiry_report() {
+    let rows = EXPIRY_REPORT.lock().unwrap();
+    if rows.is_empty() {
+        return;
+    }
+
+    let width = rows.iter().map(|row| row.map.len()).max().unwrap();
+    println!();
+    println!("expiry_foreground sweeps");
+    println!(
+        "{:width$}  {:>7}  {:>8}  {:>10}  {:>8}",
+        "map", "threads", "sweeps", "overlapped", "swept"
+    );
+    for row in rows.iter() {
+        println!(
+            "{:width$}  {:>7}  {:>8}  {:>10}  {:>8}",
+            row.map, row.threads, row.stats.sweeps, row.stats.overlapped, row.stats.swept,
+        );
+    }
+}
+
+/// Write latencies recorded by `bench_under_writes`.
+static WRITER_REPORT: Mutex<Vec<WriterRow>> = Mutex::new(Vec::new());
+
+struct WriterRow {
+    bench: &'static str,
+    map: String,
+    workers: usize,
+    stats: WriteStats,
+}
+
+/// Number and total duration of writes, split by whether a scan was running
+/// when they started.
+#[derive(Default)]
+struct WriteStats {
+    idle: (u64, Duration),
+    during_scan: (u64, Duration),
+}
+
+impl WriteStats {
+    fn record(&mut self, during_scan: bool, latency: Duration) {
+        let (writes, total) = if during_scan {
+            &mut self.during_scan
+        } else {
+            &mut self.idle
+        };
+        *writes += 1;
+        *total += latency;
+    }
+
+    fn merge(&mut self, other: &WriteStats) {
+        self.idle.0 += other.idle.0;
+        self.idle.1 += other.idle.1;
+        self.during_scan.0 += other.during_scan.0;
+        self.during_scan.1 += other.during_scan.1;
+    }
+}
+
+fn print_writer_report() {
+    let rows = WRITER_REPORT.lock().unwrap();
+    if rows.is_empty() {
+        return;
+    }
+
+    // Writers that never ran during a scan, e.g. on a machine with fewer
+    // cores than threads, have no mean to report.
+    let mean_nanos = |(writes, total): (u64, Duration)| {
+        (writes > 0).then(|| total.as_nanos() as f64 / writes as f64)
+    };
+    let show = |nanos: Option<f64>| nanos.map_or_else(|| "-".to_owned(), |n| format!("{n:.1}"));
+
+    let width = rows.iter().map(|row| row.map.len()).max().unwrap();
+    println!();
+    println!("mean write latency during scans (ns)");
+    println!(
+        "{:5}  {:width$}  {:>7}  {:>10}  {:>10}  {:>8}",
+        "bench", "map", "workers", "idle", "scanning", "slowdown"
+    );
+    for row in rows.iter() {
+        let idle = mean_nanos(row.stats.idle);
+        let during_scan = mean_nanos(row.stats.during_scan);
+        let slowdown = idle.zip(during_scan).map_or_else(
+            || "-".to_owned(),
+            |(idle, scan)| format!("{:.2}x", scan / idle),
+        );
+        println!(
+            "{:5}  {:width$}  {:>7}  {:>10}  {:>10}  {:>8}",
+            row.bench,
+            row.map,
+            row.workers,
+            show(idle),
+            show(during_scan),
+            slowdown,
+        );
+    }
+}
+
+/// A global allocator that keeps track of the number of live bytes and the
+/// total number of allocations, including reallocations.
+#[cfg(feature = "alloc-report")]
+struct CountingAlloc;
+
+#[cfg(feature = "alloc-report")]
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+#[cfg(feature = "alloc-report")]
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "alloc-report")]
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(feature = "alloc-report")]
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            LIVE_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            LIVE_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            LIVE_BYTES.fetch_add(new_size, Ordering::Relaxed);
+            LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        }
+        new_ptr
+    }
+}
+
+#[cfg(feature = "alloc-report")]
+struct FootprintRow {
+    map: String,
+    len: usize,
+    bytes: usize,
+    allocations: usize,
+}
+
+/// Populates a fresh `H` with `len` sessions and records how much memory the
+/// map holds on to afterwards, keys and values included.
+#[cfg(feature = "alloc-report")]
+fn measure_footprint<H: ConcurrentHashMap<String, String>>(len: usize) -> FootprintRow {
+    reclaim_retired();
+    let bytes_before = LIVE_BYTES.load(Ordering::Relaxed);
+    let allocations_before = ALLOCATIONS.load(Ordering::Relaxed);
+
+    let populated = H::default();
+    for n in 1..=len as u64 {
+        populated.insert(String::nth(n), "world".to_owned());
+    }
+    // Buckets that scc replaced while growing are not part of the footprint.
+    reclaim_retired();
+
+    let row = FootprintRow {
+        map: H::name(),
+        len,
+        bytes: LIVE_BYTES.load(Ordering::Relaxed) - bytes_before,
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations_before,
+    };
+    drop(populated);
+    row
+}
+
+/// Advances scc's epoch far enough that memory it retired before the call has
+/// been freed.
+#[cfg(feature = "alloc-report")]
+fn reclaim_retired() {
+    for _ in 0..4 {
+        let epoch = scc::ebr::Guard::new().epoch();
+        while scc::ebr::Guard::new().epoch() == epoch {
+            scc::ebr::Guard::new().accelerate();
+        }
+    }
+}
+
+#[cfg(feature = "alloc-report")]
+fn print_footprint_report() {
+    let measures: &[fn(usize) -> FootprintRow] = &[
+        measure_footprint::<MutexHashMap<String, String>>,
+        measure_footprint::<RwLockHashMap<String, String>>,
+        measure_footprint::<ShardedMutexHashMap<String, String, RandomState, 16>>,
+        measure_footprint::<DashMap<String, String>>,
+        measure_footprint::<scc::HashMap<String, String>>,
+        measure_footprint::<scc::HashIndex<String, String>>,
+        measure_footprint::<BoundedHashCache<String, String>>,
+    ];
+    let rows = measures
+        .iter()
+        .flat_map(|measure| FOOTPRINT_LENS.iter().map(|&len| measure(len)))
+        .collect::<Vec<_>>();
+
+    let width = rows.iter().map(|row| row.map.len()).max().unwrap();
+    println!();
+    println!("memory footprint");
+    println!(
+        "{:width$}  {:>9}  {:>12}  {:>11}  {:>11}",
+        "map", "entries", "bytes", "bytes/entry", "allocations"
+    );
+    for row in &rows {
+        println!(
+            "{:width$}  {:>9}  {:>12}  {:>11.1}  {:>11}",
+            row.map,
+            row.len,
+            row.bytes,
+            row.bytes as f64 / row.len as f64,
+            row.allocations,
+        );
+    }
+}
+
+#[cfg(feature = "latency-report")]
+struct LatencyRow {
+    map: String,
+    threads: usize,
+    histogram: LatencyHistogram,
+}
+
+/// Times each operation of a `mix` workload individually with `threads`
+/// threads sharing a populated `H`, and merges the per-thread histograms.
+///
+/// The reported latencies include the cost of reading the clock once.
+#[cfg(feature = "latency-report")]
+fn record_latencies<H: ConcurrentHashMap<String, String>>(mix: Mix, threads: usize) -> LatencyRow {
+    let map = H::default();
+
+    let keys = populate_map(&map, || "world".to_owned());
+    let key_stream = KeyStream::new(keys);
+    let ops = mix.ops();
+    let barrier = Barrier::new(threads);
+
+    let histogram = thread::scope(|s| {
+        let handles = (0..threads)
+            .map(|_| {
+                s.spawn(|| {
+                    let mut histogram = LatencyHistogram::new();
+                    barrier.wait();
+                    let ops = iter::repeat_with(|| reshuffled(&ops)).flatten();
+                    for op in ops.take(LATENCY_OPS) {
+                        let key = key_stream.next();
+                        let latency = match op {
+                            Op::Get => {
+                                let start = Instant::now();
+                                black_box(map.get(&key));
+                                start.elapsed()
+                            }
+                            Op::Insert => {
+                                let val = "world".to_owned();
+                                let start = Instant::now();
+                                black_box(map.insert(key, val));
+                                start.elapsed()
+                            }
+                            Op::Remove => {
+                                let start = Instant::now();
+                                black_box(map.remove(&key));
+                                start.elapsed()
+                            }
+                        };
+                        histogram.record(latency);
+                    }
+                    histogram
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .fold(LatencyHistogram::new(), |mut merged, histogram| {
+                merged.merge(&histogram);
+                merged
+            })
+    });
+
+    LatencyRow {
+        map: H::name(),
+        threads,
+        histogram,
+    }
+}
+
+#[cfg(feature = "latency-report")]
+fn print_latency_report() {
+    let records: &[fn(Mix, usize) -> LatencyRow] = &[
+        record_latencies::<MutexHashMap<String, String>>,
+        record_latencies::<RwLockHashMap<String, String>>,
+        record_latencies::<ShardedMutexHashMap<String, String, RandomState, 16>>,
+        record_latencies::<DashMap<String, String>>,
+        record_latencies::<scc::HashMap<String, String>>,
+    ];
+    let mixes = [Mix::new(100, 0, 0), Mix::new(0, 100, 0)]
+        .into_iter()
+        .chain(MIXES.iter().copied());
+
+    for mix in mixes {
+        let rows = records
+            .iter()
+            .flat_map(|record| {
+                LATENCY_THREADS
+                    .iter()
+                    .map(move |&threads| record(mix, threads))
+            })
+            .collect::<Vec<_>>();
+
+        let width = rows.iter().map(|row| row.map.len()).max().unwrap();
+        println!();
+        println!("latency (ns), get/insert/remove = {mix}");
+        println!(
+            "{:width$}  {:>7}  {:>8}  {:>8}  {:>8}  {:>8}",
+            "map", "threads", "p50", "p99", "p99.9", "max"
+        );
+        for row in &rows {
+            println!(
+                "{:width$}  {:>7}  {:>8}  {:>8}  {:>8}  {:>8}",
+                row.map,
+                row.threads,
+                row.histogram.percentile(50.0),
+                row.histogram.percentile(99.0),
+                row.histogram.percentile(99.9),
+                row.histogram.max,
+            );
+        }
+    }
+}
+
+/// A histogram of latencies in nanoseconds with log-linear buckets, in the
+/// style of HdrHistogram: every power of two is split into equally sized
+/// buckets, so that values are recorded with `LATENCY_PRECISION` significant
+/// bits.
+#[cfg(feature = "latency-report")]
+struct LatencyHistogram {
+    counts: Vec<u64>,
+    total: u64,
+    max: u64,
+}
+
+#[cfg(feature = "latency-report")]
+impl LatencyHistogram {
+    const HALF: usize = 1 << (LATENCY_PRECISION - 1);
+
+    fn new() -> LatencyHistogram {
+        let len = (u64::BITS - LATENCY_PRECISION + 2) as usize * Self::HALF;
+        LatencyHistogram {
+            counts: vec![0; len],
+            total: 0,
+            max: 0,
+        }
+    }
+
+    fn record(&mut self, latency: Duration) {
+        let ns = u64::try_from(latency.as_nanos()).unwrap_or(u64::MAX);
+        self.counts[Self::index(ns)] += 1;
+        self.total += 1;
+        self.max = self.max.max(ns);
+    }
+
+    fn merge(&mut self, other: &LatencyHistogram) {
+        for (count, other) in self.counts.iter_mut().zip(&other.counts) {
+            *count += other;
+        }
+        self.total += other.total;
+        self.max = self.max.max(other.max);
+    }
+
+    /// The smallest recorded latency that is at least as large as `p`
+    /// percent of all recorded latencies, rounded up to its bucket's upper
+    /// bound.
+    fn percentile(&self, p: f64) -> u64 {
+        let rank = ((p / 100.0 * self.total as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (index, &count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return Self::upper_bound(index).min(self.max);
+            }
+        }
+        self.max
+    }
+
+    fn index(ns: u64) -> usize {
+        let shift = (u64::BITS - ns.leading_zeros()).saturating_sub(LATENCY_PRECISION);
+        // For a non-zero shift, `ns >> shift` lies in `HALF..2 * HALF`.
+        (shift as usize * Self::HALF) + (ns >> shift) as usize
+    }
+
+    fn upper_bound(index: usize) -> u64 {
+        if index < 2 * Self::HALF {
+            return index as u64;
+        }
+        let shift = index / Self::HALF - 1;
+        let mantissa = (index - shift * Self::HALF) as u64;
+        ((mantissa + 1) << shift).wrapping_sub(1)
+    }
+}
+
+/// A string value of exactly `size` bytes.
+fn sized_value(size: usize) -> String {
+    "w".repeat(size)
+}
+
+/// Populates `map` with sessions whose expiry times are spread evenly over
+/// the next `ttl`.
+fn populate_sessions(map: &impl ConcurrentHashMap<String, Session>, ttl: Duration) -> Vec<String> {
+    let now = Instant::now();
+    let keys = (1..=NUM_KEYS).map(|n| n.to_string()).collect::<Vec<_>>();
+
+    for (i, key) in (1..).zip(&keys) {
+        let session = Session {
+            data: "world".to_owned(),
+            expires_at: now + ttl * i / NUM_KEYS as u32,
+        };
+        map.insert(key.clone(), session);
+    }
+
+    keys
+}
+
+/// A cyclic source of benchmark keys in which every thread walks the whole
+/// key set from its own starting point.
+///
+/// A thread picks its starting point on its first call to `next` and from
+/// then on reads keys without any synchronization. Starting points are
+/// spread evenly over the keys, so that threads start out on different
+/// entries.
+struct KeyStream<T> {
+    id: usize,
+    keys: Vec<T>,
+    /// Number of starting points, the largest number of threads expected to
+    /// read from the stream.
+    num_starts: usize,
+    next_start: AtomicUsize,
+}
+
+#[derive(Clone, Copy)]
+struct KeyCursor {
+    stream: usize,
+    pos: usize,
+}
+
+static NEXT_STREAM_ID: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static KEY_CURSOR: Cell<KeyCursor> = const {
+        Cell::new(KeyCursor {
+            stream: usize::MAX,
+            pos: 0,
+        })
+    };
+}
+
+impl<T: Clone> KeyStream<T> {
+    fn new(keys: Vec<T>) -> KeyStream<T> {
+        assert!(!keys.is_empty(), "`KeyStream` requires at least one key");
+
+        let num_starts = THREADS
+            .iter()
+            .copied()
+            .chain(thread::available_parallelism().map(Into::into))
+            .max()
+            .unwrap();
+
+        KeyStream {
+            id: NEXT_STREAM_ID.fetch_add(1, Ordering::Relaxed),
+            keys,
+            num_starts,
+            next_start: AtomicUsize::new(0),
+        }
+    }
+
+    /// Number of threads that have read from the stream so far.
+    fn readers(&self) -> usize {
+        self.next_start.load(Ordering::Relaxed)
+    }
+
+    fn next(&self) -> T {
+        KEY_CURSOR.with(|cell| {
+            let mut cursor = cell.get();
+            if cursor.stream != self.id {
+                let start = self.next_start.fetch_add(1, Ordering::Relaxed) % self.num_starts;
+                cursor = KeyCursor {
+                    stream: self.id,
+                    pos: start * self.keys.len() / self.num_starts,
+                };
+            }
+
+            let key = self.keys[cursor.pos].clone();
+            cursor.pos = (cursor.pos + 1) % self.keys.len();
+            cell.set(cursor);
+
+            key
+        })
+    }
+}
+
+/// Sessions along with an index from user ID to the IDs of that user's
+/// sessions, so that all sessions of a user can be revoked at once.
+///
+/// Sessions are only created or revoked while their user's index entry is
+/// locked, which makes a logout atomic with respect to concurrent creation:
+/// a session created for the user either gets revoked or is created after
+/// the logout.
+struct UserSessionStore<H: UserIndexed> {
+    sessions: H,
+    index: H::Index,
+}
+
+impl<H: UserIndexed + Default> Default for UserSessionStore<H> {
+    fn default() -> Self {
+        UserSessionStore {
+            sessions: H::default(),
+            index: H::Index::default(),
+        }
+    }
+}
+
+impl<H> UserSessionStore<H>
+where
+    H: UserIndexed + ConcurrentHashMap<String, UserSession>,
+{
+    fn create(&self, id: String, session: UserSession) {
+        let user_id = session.user_id;
+        // Index entries are never removed, so the update below always finds
+        // one.
+        self.index.insert_if_absent(user_id, Vec::new());
+        self.index.update(&user_id, |ids| {
+            self.sessions.insert(id.clone(), session);
+            ids.push(id);
+        });
+    }
+
+    /// Creates every session of `user_id` listed by `user_session_ids`.
+    fn create_all(&self, user_id: u64) {
+        for id in user_session_ids(user_id) {
+            self.create(id, UserSession::new(user_id));
+        }
+    }
+
+    /// Revokes every session of `user_id` through the index, returning how
+    /// many were revoked.
+    fn logout(&self, user_id: u64) -> usize {
+        self.index
+            .update(&user_id, |ids| {
+                let revoked = ids.len();
+                for id in ids.drain(..) {
+                    self.sessions.remove(&id);
+                }
+                revoked
+            })
+            .unwrap_or(0)
+    }
+
+    /// Revokes every session of `user_id` by checking all sessions.
+    fn logout_by_scan(&self, user_id: u64) {
+        self.sessions
+            .retain(|_, session| session.user_id != user_id);
+    }
+}
+
+/// The IDs of the `SESSIONS_PER_USER` sessions of `user_id`.
+fn user_session_ids(user_id: u64) -> impl Iterator<Item = String> {
+    (0..SESSIONS_PER_USER).map(move |n| format!("{user_id}-{n}"))
+}
+
+#[derive(Clone, Debug)]
+struct UserSession {
+    user_id: u64,
+    #[allow(dead_code)]
+    data: String,
+}
+
+impl UserSession {
+    fn new(user_id: u64) -> UserSession {
+        UserSession {
+            user_id,
+            data: "world".to_owned(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Session {
+    #[allow(dead_code)]
+    data: String,
+    expires_at: Instant,
+}
+
+impl Session {
+    fn new(ttl: Duration) -> Session {
+        Session {
+            data: "world".to_owned(),
+            expires_at: Instant::now() + ttl,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Op {
+    Get,
+    Insert,
+    Remove,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Mix {
+    get: usize,
+    insert: usize,
+    remove: usize,
+}
+
+impl Mix {
+    const fn new(get: usize, insert: usize, remove: usize) -> Mix {
+        assert!(get + insert + remove == 100);
+        Mix {
+            get,
+            insert,
+            remove,
+        }
+    }
+
+    /// `BATCH_SIZE` operations in the ratio described by `self`, shuffled so
+    /// that writes are interleaved with reads.
+    fn ops(&self) -> Vec<Op> {
+        let mut ops = iter::repeat_n(Op::Get, self.get)
+            .chain(iter::repeat_n(Op::Insert, self.insert))
+            .chain(iter::repeat_n(Op::Remove, self.remove))
+            .collect::<Vec<_>>();
+        debug_assert_eq!(ops.len(), BATCH_SIZE);
+        ops.shuffle(&mut SmallRng::seed_from_u64(SEED));
+        ops
+    }
+}
+
+thread_local! {
+    static OPS_RNG: RefCell<SmallRng> = RefCell::new(SmallRng::seed_from_u64(SEED));
+}
+
+/// A copy of `ops` in a new order, drawn for every batch of a mixed workload.
+///
+/// Key sets are a multiple of `BATCH_SIZE` long, so with a fixed order every
+/// key would always get the same operation, e.g. some sessions would never be
+/// refreshed and others never be read.
+fn reshuffled(ops: &[Op]) -> Vec<Op> {
+    let mut ops = ops.to_vec();
+    OPS_RNG.with_borrow_mut(|rng| ops.shuffle(rng));
+    ops
+}
+
+impl fmt::Display for Mix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}/{}", self.get, self.insert, self.remove)
+    }
+}
+
+/// How benchmark keys are picked from the populated key set.
+#[derive(Clone, Copy, Debug)]
+enum KeyDist {
+    /// Every key is equally likely.
+    Uniform,
+    /// The key of rank `k` is picked with probability proportional to
+    /// `1 / k^s`, where `s` is the exponent.
+    Zipf(f64),
+    /// Every access goes to the same key.
+    Hot,
+}
+
+impl KeyDist {
+    /// Draws `NUM_SAMPLES` keys from `keys` according to `self`.
+    fn sample<T: Clone>(&self, keys: &[T]) -> Vec<T> {
+        let mut rng = SmallRng::seed_from_u64(SEED);
+
+        match *self {
+            KeyDist::Uniform => iter::repeat_with(|| keys.choose(&mut rng).unwrap().clone())
+                .take(NUM_SAMPLES)
+                .collect(),
+            KeyDist::Zipf(s) => {
+                let zipf = Zipf::new(keys.len() as f64, s).unwrap();
+                zipf.sample_iter(rng)
+                    .take(NUM_SAMPLES)
+                    .map(|rank| keys[rank as usize - 1].clone())
+                    .collect()
+            }
+            KeyDist::Hot => vec![keys[0].clone(); NUM_SAMPLES],
+        }
+    }
+}
+
+impl fmt::Display for KeyDist {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyDist::Uniform => f.write_str("uniform"),
+            KeyDist::Zipf(s) => write!(f, "zipf(s={s})"),
+            KeyDist::Hot => f.write_str("hot"),
+        }
+    }
+}
+
+/// Percentage of lookups that miss.
+#[derive(Clone, Copy, Debug)]
+struct MissRatio(u32);
+
+impl MissRatio {
+    /// Draws `NUM_SAMPLES` keys, each of which is either one of `keys` or,
+    /// with the probability given by `self`, a key of the same shape that
+    /// was never inserted.
+    fn sample<T: BenchKey>(&self, keys: &[T]) -> Vec<T> {
+        let mut rng = SmallRng::seed_from_u64(SEED);
+        let num_keys = keys.len() as u64;
+
+        iter::repeat_with(|| {
+            if rng.random_ratio(self.0, 100) {
+                T::nth(num_keys + rng.random_range(1..=num_keys))
+            } else {
+                keys.choose(&mut rng).unwrap().clone()
+            }
+        })
+        .take(NUM_SAMPLES)
+        .collect()
+    }
+}
+
+impl fmt::Display for MissRatio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}%", self.0)
+    }
+}
+
+/// How all sessions of a user are revoked.
+#[derive(Clone, Copy, Debug)]
+enum Logout {
+    /// Checks every session with `ConcurrentHashMap::retain`.
+    Scan,
+    /// Looks up the user's sessions in a user-ID index.
+    Index,
+}
+
+impl fmt::Display for Logout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Logout::Scan => f.write_str("scan"),
+            Logout::Index => f.write_str("index"),
+        }
     }
 }
@@ -1,416 +1,677 @@
-use std::{collections::HashMap, hint::black_box, iter};
+use std::{collections::HashMap, fmt, hint::black_box};
 
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use criterion::{
-    criterion_group, criterion_main, measurement::WallTime, BatchSize, BenchmarkGroup, Criterion,
+    criterion_group, measurement::WallTime, BatchSize, BenchmarkGroup, BenchmarkId, Criterion,
+    Throughput,
 };
-use serde::{Deserialize, Serialize};
+use rand::{
+    distr::{Alphanumeric, SampleString},
+    rngs::SmallRng,
+    seq::IndexedRandom,
+    Rng, SeedableRng,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+use serde_json::value::RawValue;
+
+const SEED: u64 = 0x5e55_1011;
+
+const SIZES: [FixtureSize; 3] = [FixtureSize::Small, FixtureSize::Medium, FixtureSize::Large];
 
+/// Lifetime of an authenticated session, in seconds.
+const SESSION_TTL: u64 = 24 * 60 * 60;
+
+/// Seconds since the Unix epoch around which fixture timestamps are drawn.
+const NOW: u64 = 1_750_000_000;
+
+/// The session of a logged-in user.
 #[derive(Clone, Debug, Deserialize, Serialize)]
-struct Data {
-    s: String,
-    p: (u64, u64, u64),
+struct AuthSession {
+    user_id: u64,
+    roles: Vec<String>,
+    issued_at: u64,
+    expires_at: u64,
+    csrf_token: String,
 }
 
-impl Data {
-    fn sample() -> Self {
-        Data {
-            s: "hello, world!".into(),
-            p: (128, 512, 1024),
-        }
-    }
+/// Messages queued for display on the next page load.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct FlashQueue {
+    messages: Vec<FlashMessage>,
+}
 
-    fn sample_vec(n: usize) -> Vec<Self> {
-        iter::repeat_with(Data::sample).take(n).collect()
-    }
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct FlashMessage {
+    level: FlashLevel,
+    text: String,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum FlashLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
-struct ComplexData {
-    deeply: HashMap<String, Vec<HashMap<String, u8>>>,
+struct ShoppingCart {
+    currency: String,
+    items: Vec<LineItem>,
+    coupon: Option<String>,
 }
 
-impl ComplexData {
-    fn sample() -> Self {
-        let data = HashMap::from([
-            ("value".into(), 4),
-            ("another".into(), 6),
-            ("yet_another".into(), 7),
-        ]);
-        let data = iter::repeat(data).take(10).collect::<Vec<_>>();
-        let data = ["nested".into(), "data".into(), "is".into(), "cool".into()]
-            .into_iter()
-            .zip(iter::repeat(data))
-            .collect::<HashMap<_, _>>();
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct LineItem {
+    sku: String,
+    name: String,
+    quantity: u32,
+    unit_price_cents: u64,
+}
 
-        ComplexData { deeply: data }
+/// State kept between redirecting to an OAuth provider and handling its
+/// callback.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct OAuthState {
+    state: String,
+    pkce_verifier: String,
+    nonce: String,
+    redirect_uri: String,
+    scopes: Vec<String>,
+    created_at: u64,
+}
+
+/// How much data a fixture holds, e.g. the number of roles of an
+/// `AuthSession` or of items in a `ShoppingCart`.
+#[derive(Clone, Copy, Debug)]
+enum FixtureSize {
+    Small,
+    Medium,
+    Large,
+}
+
+impl FixtureSize {
+    fn len(self) -> usize {
+        match self {
+            FixtureSize::Small => 1,
+            FixtureSize::Medium => 8,
+            FixtureSize::Large => 64,
+        }
     }
 }
 
-const SAMPLE_SIZE: usize = 50;
+impl fmt::Display for FixtureSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixtureSize::Small => f.write_str("small"),
+            FixtureSize::Medium => f.write_str("medium"),
+            FixtureSize::Large => f.write_str("large"),
+        }
+    }
+}
 
-fn serialize_simple_direct(g: &mut BenchmarkGroup<WallTime>) {
-    let data = Data::sample();
+/// Generates the fixture of the given size, the same for every run.
+fn fixture<P: Payload>(size: FixtureSize) -> P {
+    P::generate(size, &mut SmallRng::seed_from_u64(SEED))
+}
 
-    g.bench_function("direct", |b| {
-        b.iter(|| {
-            black_box(serde_json::to_string(black_box(&data)).unwrap());
+/// A random token of `bytes` bytes, encoded as in a cookie or URL.
+fn token(rng: &mut SmallRng, bytes: usize) -> String {
+    let bytes = (0..bytes).map(|_| rng.random()).collect::<Vec<u8>>();
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Random text of `words` words.
+fn text(rng: &mut SmallRng, words: usize) -> String {
+    (0..words)
+        .map(|_| {
+            let len = rng.random_range(2..10);
+            Alphanumeric.sample_string(rng, len)
         })
-    });
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
-fn serialize_simple_value(g: &mut BenchmarkGroup<WallTime>) {
-    let data = Data::sample();
+/// A format that records are stored in.
+trait Codec {
+    const NAME: &'static str;
 
-    g.bench_function("value", |b| {
-        b.iter_batched(
-            || HashMap::<String, serde_json::Value>::from([("data".into(), Default::default())]),
-            |mut map| {
-                map.insert(
-                    "data".into(),
-                    serde_json::to_value(black_box(&data)).unwrap(),
-                );
-                black_box(serde_json::to_string(&map).unwrap());
-            },
-            BatchSize::SmallInput,
-        )
-    });
+    /// How an entry that was encoded on its own is embedded in a record, for
+    /// the `string` strategy.
+    type Nested: Serialize + DeserializeOwned + Default + AsRef<[u8]>;
+
+    fn encode<T: Serialize + ?Sized>(value: &T) -> Vec<u8>;
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> T;
+    fn encode_nested<T: Serialize + ?Sized>(value: &T) -> Self::Nested;
+    fn decode_nested<T: DeserializeOwned>(nested: &Self::Nested) -> T;
 }
 
-fn serialize_simple_string(g: &mut BenchmarkGroup<WallTime>) {
-    let data = Data::sample();
+struct Json;
 
-    g.bench_function("string", |b| {
-        b.iter_batched(
-            || HashMap::<String, String>::from([("data".into(), Default::default())]),
-            |mut map| {
-                map.insert(
-                    "data".into(),
-                    serde_json::to_string(black_box(&data)).unwrap(),
-                );
-                black_box(serde_json::to_string(&map).unwrap());
-            },
-            BatchSize::SmallInput,
-        )
-    });
+impl Codec for Json {
+    const NAME: &'static str = "json";
+
+    type Nested = String;
+
+    fn encode<T: Serialize + ?Sized>(value: &T) -> Vec<u8> {
+        serde_json::to_vec(value).unwrap()
+    }
+
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> T {
+        serde_json::from_slice(bytes).unwrap()
+    }
+
+    fn encode_nested<T: Serialize + ?Sized>(value: &T) -> String {
+        serde_json::to_string(value).unwrap()
+    }
+
+    fn decode_nested<T: DeserializeOwned>(nested: &String) -> T {
+        serde_json::from_str(nested).unwrap()
+    }
 }
 
-fn serialize_big_direct(g: &mut BenchmarkGroup<WallTime>) {
-    let data = Data::sample_vec(SAMPLE_SIZE);
+/// MessagePack with structs encoded as arrays of their fields.
+struct MsgPack;
 
-    g.bench_function("direct", |b| {
-        b.iter(|| {
-            black_box(serde_json::to_string(black_box(&data)).unwrap());
-        })
-    });
+impl Codec for MsgPack {
+    const NAME: &'static str = "msgpack";
+
+    type Nested = ByteBuf;
+
+    fn encode<T: Serialize + ?Sized>(value: &T) -> Vec<u8> {
+        rmp_serde::to_vec(value).unwrap()
+    }
+
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> T {
+        rmp_serde::from_slice(bytes).unwrap()
+    }
+
+    fn encode_nested<T: Serialize + ?Sized>(value: &T) -> ByteBuf {
+        ByteBuf::from(Self::encode(value))
+    }
+
+    fn decode_nested<T: DeserializeOwned>(nested: &ByteBuf) -> T {
+        Self::decode(nested)
+    }
 }
 
-fn serialize_big_value(g: &mut BenchmarkGroup<WallTime>) {
-    let data = Data::sample_vec(SAMPLE_SIZE);
+/// MessagePack with structs encoded as maps keyed by field name.
+struct MsgPackNamed;
 
-    g.bench_function("value", |b| {
-        b.iter_batched(
-            || HashMap::<String, serde_json::Value>::from([("data".into(), Default::default())]),
-            |mut map| {
-                map.insert(
-                    "data".into(),
-                    serde_json::to_value(black_box(&data)).unwrap(),
-                );
-                black_box(serde_json::to_string(&map).unwrap());
-            },
-            BatchSize::SmallInput,
-        )
-    });
+impl Codec for MsgPackNamed {
+    const NAME: &'static str = "msgpack_named";
+
+    type Nested = ByteBuf;
+
+    fn encode<T: Serialize + ?Sized>(value: &T) -> Vec<u8> {
+        rmp_serde::to_vec_named(value).unwrap()
+    }
+
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> T {
+        rmp_serde::from_slice(bytes).unwrap()
+    }
+
+    fn encode_nested<T: Serialize + ?Sized>(value: &T) -> ByteBuf {
+        ByteBuf::from(Self::encode(value))
+    }
+
+    fn decode_nested<T: DeserializeOwned>(nested: &ByteBuf) -> T {
+        Self::decode(nested)
+    }
 }
 
-fn serialize_big_string(g: &mut BenchmarkGroup<WallTime>) {
-    let data = Data::sample_vec(SAMPLE_SIZE);
+/// Data stored under a single key of a record.
+trait Payload: Serialize + DeserializeOwned {
+    const NAME: &'static str;
 
-    g.bench_function("string", |b| {
-        b.iter_batched(
-            || HashMap::<String, String>::from([("data".into(), Default::default())]),
-            |mut map| {
-                map.insert(
-                    "data".into(),
-                    serde_json::to_string(black_box(&data)).unwrap(),
-                );
-                black_box(serde_json::to_string(&map).unwrap());
-            },
-            BatchSize::SmallInput,
-        )
-    });
+    fn generate(size: FixtureSize, rng: &mut SmallRng) -> Self;
+
+    /// Reads one field, as a handler would.
+    fn read(&self);
+    fn read_value(value: &serde_json::Value);
+
+    /// Modifies one field, as a handler would.
+    fn write(&mut self);
+    fn write_value(value: &mut serde_json::Value);
 }
 
-fn serialize_complex_direct(g: &mut BenchmarkGroup<WallTime>) {
-    let data = ComplexData::sample();
+impl Payload for AuthSession {
+    const NAME: &'static str = "auth";
 
-    g.bench_function("direct", |b| {
-        b.iter(|| {
-            black_box(serde_json::to_string(black_box(&data)).unwrap());
-        })
-    });
+    fn generate(size: FixtureSize, rng: &mut SmallRng) -> Self {
+        let issued_at = NOW - rng.random_range(0..SESSION_TTL);
+        AuthSession {
+            user_id: rng.random(),
+            roles: (0..size.len()).map(|_| text(rng, 1)).collect(),
+            issued_at,
+            expires_at: issued_at + SESSION_TTL,
+            csrf_token: token(rng, 32),
+        }
+    }
+
+    /// Checks the CSRF token of a request.
+    fn read(&self) {
+        black_box(&self.csrf_token);
+    }
+
+    fn read_value(value: &serde_json::Value) {
+        let token = value.get("csrf_token").and_then(|v| v.as_str()).unwrap();
+        black_box(token);
+    }
+
+    /// Extends the session.
+    fn write(&mut self) {
+        self.expires_at = black_box(NOW) + SESSION_TTL;
+    }
+
+    fn write_value(value: &mut serde_json::Value) {
+        let expires_at = value.get_mut(black_box("expires_at")).unwrap();
+        *expires_at = (black_box(NOW) + SESSION_TTL).into();
+    }
 }
 
-fn serialize_complex_value(g: &mut BenchmarkGroup<WallTime>) {
-    let data = ComplexData::sample();
+impl Payload for FlashQueue {
+    const NAME: &'static str = "flash";
+
+    fn generate(size: FixtureSize, rng: &mut SmallRng) -> Self {
+        let levels = [
+            FlashLevel::Info,
+            FlashLevel::Success,
+            FlashLevel::Warning,
+            FlashLevel::Error,
+        ];
+        FlashQueue {
+            messages: (0..size.len())
+                .map(|_| FlashMessage {
+                    level: *levels.choose(rng).unwrap(),
+                    text: text(rng, 8),
+                })
+                .collect(),
+        }
+    }
 
-    g.bench_function("value", |b| {
-        b.iter_batched(
-            || HashMap::<String, serde_json::Value>::from([("data".into(), Default::default())]),
-            |mut map| {
-                map.insert(
-                    "data".into(),
-                    serde_json::to_value(black_box(&data)).unwrap(),
-                );
-                black_box(serde_json::to_string(&map).unwrap());
-            },
-            BatchSize::SmallInput,
-        )
-    });
+    /// Shows the oldest message.
+    fn read(&self) {
+        black_box(&self.messages[0].text);
+    }
+
+    fn read_value(value: &serde_json::Value) {
+        let text = value
+            .get("messages")
+            .and_then(|v| v.get(0))
+            .and_then(|v| v.get("text"))
+            .and_then(|v| v.as_str())
+            .unwrap();
+        black_box(text);
+    }
+
+    /// Queues another message.
+    fn write(&mut self) {
+        self.messages.push(FlashMessage {
+            level: FlashLevel::Success,
+            text: black_box("Your changes have been saved.").into(),
+        });
+    }
+
+    fn write_value(value: &mut serde_json::Value) {
+        let messages = value
+            .get_mut(black_box("messages"))
+            .and_then(|v| v.as_array_mut())
+            .unwrap();
+        messages.push(serde_json::json!({
+            "level": "success",
+            "text": black_box("Your changes have been saved."),
+        }));
+    }
 }
 
-fn serialize_complex_string(g: &mut BenchmarkGroup<WallTime>) {
-    let data = ComplexData::sample();
+impl Payload for ShoppingCart {
+    const NAME: &'static str = "cart";
+
+    fn generate(size: FixtureSize, rng: &mut SmallRng) -> Self {
+        ShoppingCart {
+            currency: "EUR".into(),
+            items: (0..size.len())
+                .map(|_| LineItem {
+                    sku: Alphanumeric.sample_string(rng, 12),
+                    name: text(rng, 4),
+                    quantity: rng.random_range(1..=5),
+                    unit_price_cents: rng.random_range(100..100_000),
+                })
+                .collect(),
+            coupon: rng
+                .random_bool(0.5)
+                .then(|| Alphanumeric.sample_string(rng, 8)),
+        }
+    }
 
-    g.bench_function("string", |b| {
-        b.iter_batched(
-            || HashMap::<String, String>::from([("data".into(), Default::default())]),
-            |mut map| {
-                map.insert(
-                    "data".into(),
-                    serde_json::to_string(black_box(&data)).unwrap(),
-                );
-                black_box(serde_json::to_string(&map).unwrap());
-            },
-            BatchSize::SmallInput,
-        )
-    });
+    /// Reads the quantity of the item in the middle of the cart.
+    fn read(&self) {
+        black_box(self.items[self.items.len() / 2].quantity);
+    }
+
+    fn read_value(value: &serde_json::Value) {
+        let items = value.get("items").and_then(|v| v.as_array()).unwrap();
+        let quantity = items[items.len() / 2]
+            .get("quantity")
+            .and_then(|v| v.as_u64())
+            .unwrap();
+        black_box(quantity);
+    }
+
+    /// Adds one to the quantity of the item in the middle of the cart.
+    fn write(&mut self) {
+        let mid = self.items.len() / 2;
+        self.items[black_box(mid)].quantity += 1;
+    }
+
+    fn write_value(value: &mut serde_json::Value) {
+        let items = value
+            .get_mut(black_box("items"))
+            .and_then(|v| v.as_array_mut())
+            .unwrap();
+        let mid = items.len() / 2;
+        let quantity = items[black_box(mid)]
+            .get_mut(black_box("quantity"))
+            .unwrap();
+        *quantity = (quantity.as_u64().unwrap() + 1).into();
+    }
 }
 
-fn deserialize_simple_direct(g: &mut BenchmarkGroup<WallTime>) {
-    let data = Data::sample();
-    let buf = serde_json::to_string(&data).unwrap();
+impl Payload for OAuthState {
+    const NAME: &'static str = "oauth";
 
-    g.bench_function("direct", |b| {
-        b.iter(|| {
-            let mut data: Data = serde_json::from_str(black_box(&buf)).unwrap();
-            black_box(&mut data);
-        })
-    });
+    fn generate(size: FixtureSize, rng: &mut SmallRng) -> Self {
+        OAuthState {
+            state: token(rng, 16),
+            pkce_verifier: token(rng, 32),
+            nonce: token(rng, 16),
+            redirect_uri: format!("https://example.com/{}/callback", text(rng, 1)),
+            scopes: (0..size.len()).map(|_| text(rng, 1)).collect(),
+            created_at: NOW - rng.random_range(0..600),
+        }
+    }
+
+    /// Checks the state of a callback.
+    fn read(&self) {
+        black_box(&self.state);
+    }
+
+    fn read_value(value: &serde_json::Value) {
+        let state = value.get("state").and_then(|v| v.as_str()).unwrap();
+        black_box(state);
+    }
+
+    /// Replaces the nonce for another authorization request.
+    fn write(&mut self) {
+        self.nonce = black_box("bm9uY2Utb2YtdGhlLW5leHQtcmVxdWVzdA").into();
+    }
+
+    fn write_value(value: &mut serde_json::Value) {
+        let nonce = value.get_mut(black_box("nonce")).unwrap();
+        *nonce = black_box("bm9uY2Utb2YtdGhlLW5leHQtcmVxdWVzdA").into();
+    }
 }
 
-fn deserialize_simple_value(g: &mut BenchmarkGroup<WallTime>) {
-    let data = Data::sample();
-    type Map = HashMap<String, serde_json::Value>;
-    let map: Map = HashMap::from([("data".into(), serde_json::to_value(data).unwrap())]);
-    let buf = serde_json::to_string(&map).unwrap();
+/// A record holding a payload as its only entry, encoded according to each
+/// strategy.
+struct EncodedRecord {
+    direct: Vec<u8>,
+    value: Vec<u8>,
+    string: Vec<u8>,
+}
 
-    g.bench_function("value", |b| {
-        b.iter(|| {
-            let map: Map = serde_json::from_str(black_box(&buf)).unwrap();
-            let mut data: Data = map
-                .get("data")
-                .and_then(|value| serde_json::from_value(value.clone()).ok())
-                .unwrap();
-            black_box(&mut data);
-        })
-    });
+impl EncodedRecord {
+    fn new<C: Codec, P: Payload>(data: &P) -> EncodedRecord {
+        let value = HashMap::<String, serde_json::Value>::from([(
+            "data".into(),
+            serde_json::to_value(data).unwrap(),
+        )]);
+        let string = HashMap::<String, C::Nested>::from([("data".into(), C::encode_nested(data))]);
+
+        EncodedRecord {
+            direct: C::encode(data),
+            value: C::encode(&value),
+            string: C::encode(&string),
+        }
+    }
 }
 
-fn deserialize_simple_string(g: &mut BenchmarkGroup<WallTime>) {
-    let data = Data::sample();
-    type Map = HashMap<String, String>;
-    let map: Map = HashMap::from([("data".into(), serde_json::to_string(&data).unwrap())]);
-    let buf = serde_json::to_string(&map).unwrap();
+/// A record for the `raw` strategy, in which each entry stays undecoded JSON
+/// until it is accessed.
+///
+/// `RawValue` only exists for JSON, so unlike the other strategies this one
+/// is not generic over the codec.
+type RawRecord = HashMap<String, Box<RawValue>>;
+
+/// A `RawRecord` holding a payload as its only entry.
+fn encode_raw_record<P: Payload>(data: &P) -> Vec<u8> {
+    let record = RawRecord::from([(
+        "data".into(),
+        serde_json::value::to_raw_value(data).unwrap(),
+    )]);
+    Json::encode(&record)
+}
+
+/// A record holding a fixture of every payload under its own key, as the
+/// session of a user with several entries would, encoded according to each
+/// strategy that keeps entries apart.
+struct EncodedSession {
+    value: Vec<u8>,
+    string: Vec<u8>,
+    raw: Vec<u8>,
+}
+
+impl EncodedSession {
+    fn new<C: Codec>(size: FixtureSize) -> EncodedSession {
+        let mut session = SessionEntries::<C>::default();
+        session.insert::<AuthSession>(size);
+        session.insert::<FlashQueue>(size);
+        session.insert::<ShoppingCart>(size);
+        session.insert::<OAuthState>(size);
+
+        EncodedSession {
+            value: C::encode(&session.value),
+            string: C::encode(&session.string),
+            raw: Json::encode(&session.raw),
+        }
+    }
+}
 
-    g.bench_function("string", |b| {
-        b.iter(|| {
-            let map: Map = serde_json::from_str(black_box(&buf)).unwrap();
-            let mut data: Data = map
-                .get("data")
-                .and_then(|s| serde_json::from_str(s).ok())
-                .unwrap();
-            black_box(&mut data);
-        })
-    });
+/// The entries of an `EncodedSession` before the record is encoded.
+struct SessionEntries<C: Codec> {
+    value: HashMap<String, serde_json::Value>,
+    string: HashMap<String, C::Nested>,
+    raw: RawRecord,
 }
 
-fn deserialize_big_direct(g: &mut BenchmarkGroup<WallTime>) {
-    let data = Data::sample_vec(SAMPLE_SIZE);
-    let buf = serde_json::to_string(&data).unwrap();
+impl<C: Codec> Default for SessionEntries<C> {
+    fn default() -> Self {
+        SessionEntries {
+            value: HashMap::new(),
+            string: HashMap::new(),
+            raw: RawRecord::new(),
+        }
+    }
+}
 
-    g.bench_function("direct", |b| {
-        b.iter(|| {
-            let mut data: Vec<Data> = serde_json::from_str(black_box(&buf)).unwrap();
-            black_box(&mut data);
-        })
-    });
+impl<C: Codec> SessionEntries<C> {
+    fn insert<P: Payload>(&mut self, size: FixtureSize) {
+        let data = fixture::<P>(size);
+        self.value
+            .insert(P::NAME.into(), serde_json::to_value(&data).unwrap());
+        self.string.insert(P::NAME.into(), C::encode_nested(&data));
+        self.raw.insert(
+            P::NAME.into(),
+            serde_json::value::to_raw_value(&data).unwrap(),
+        );
+    }
+}
+
+fn bytes(buf: &[u8]) -> Throughput {
+    Throughput::Bytes(buf.len() as u64)
 }
 
-fn deserialize_big_value(g: &mut BenchmarkGroup<WallTime>) {
-    let data = Data::sample_vec(SAMPLE_SIZE);
-    type Map = HashMap<String, serde_json::Value>;
-    let map: Map = HashMap::from([("data".into(), serde_json::to_value(data).unwrap())]);
-    let buf = serde_json::to_string(&map).unwrap();
+fn serialize<C: Codec, P: Payload>(g: &mut BenchmarkGroup<WallTime>, data: &P) {
+    let record = EncodedRecord::new::<C, P>(data);
 
-    g.bench_function("value", |b| {
+    g.throughput(bytes(&record.direct));
+    g.bench_function(BenchmarkId::new("direct", C::NAME), |b| {
         b.iter(|| {
-            let map: Map = serde_json::from_str(black_box(&buf)).unwrap();
-            let mut data: Vec<Data> = map
-                .get("data")
-                .and_then(|value| serde_json::from_value(value.clone()).ok())
-                .unwrap();
-            black_box(&mut data);
+            black_box(C::encode(black_box(data)));
         })
     });
-}
 
-fn deserialize_big_string(g: &mut BenchmarkGroup<WallTime>) {
-    let data = Data::sample_vec(SAMPLE_SIZE);
-    type Map = HashMap<String, String>;
-    let map: Map = HashMap::from([("data".into(), serde_json::to_string(&data).unwrap())]);
-    let buf = serde_json::to_string(&map).unwrap();
+    g.throughput(bytes(&record.value));
+    g.bench_function(BenchmarkId::new("value", C::NAME), |b| {
+        b.iter_batched(
+            || HashMap::<String, serde_json::Value>::from([("data".into(), Default::default())]),
+            |mut map| {
+                map.insert(
+                    "data".into(),
+                    serde_json::to_value(black_box(data)).unwrap(),
+                );
+                black_box(C::encode(&map));
+            },
+            BatchSize::SmallInput,
+        )
+    });
 
-    g.bench_function("string", |b| {
-        b.iter(|| {
-            let map: Map = serde_json::from_str(black_box(&buf)).unwrap();
-            let mut data: Vec<Data> = map
-                .get("data")
-                .and_then(|s| serde_json::from_str(s).ok())
-                .unwrap();
-            black_box(&mut data);
-        })
+    g.throughput(bytes(&record.string));
+    g.bench_function(BenchmarkId::new("string", C::NAME), |b| {
+        b.iter_batched(
+            || HashMap::<String, C::Nested>::from([("data".into(), Default::default())]),
+            |mut map| {
+                map.insert("data".into(), C::encode_nested(black_box(data)));
+                black_box(C::encode(&map));
+            },
+            BatchSize::SmallInput,
+        )
     });
 }
 
-fn deserialize_complex_direct(g: &mut BenchmarkGroup<WallTime>) {
-    let data = ComplexData::sample();
-    let buf = serde_json::to_string(&data).unwrap();
+fn deserialize<C: Codec, P: Payload>(g: &mut BenchmarkGroup<WallTime>, data: &P) {
+    let record = EncodedRecord::new::<C, P>(data);
 
-    g.bench_function("direct", |b| {
+    g.throughput(bytes(&record.direct));
+    g.bench_function(BenchmarkId::new("direct", C::NAME), |b| {
         b.iter(|| {
-            let mut data: ComplexData = serde_json::from_str(black_box(&buf)).unwrap();
+            let mut data: P = C::decode(black_box(&record.direct));
             black_box(&mut data);
         })
     });
-}
 
-fn deserialize_complex_value(g: &mut BenchmarkGroup<WallTime>) {
-    let data = ComplexData::sample();
-    type Map = HashMap<String, serde_json::Value>;
-    let map: Map = HashMap::from([("data".into(), serde_json::to_value(data).unwrap())]);
-    let buf = serde_json::to_string(&map).unwrap();
-
-    g.bench_function("value", |b| {
+    g.throughput(bytes(&record.value));
+    g.bench_function(BenchmarkId::new("value", C::NAME), |b| {
         b.iter(|| {
-            let map: Map = serde_json::from_str(black_box(&buf)).unwrap();
-            let mut data: ComplexData = map
+            let map: HashMap<String, serde_json::Value> = C::decode(black_box(&record.value));
+            let mut data: P = map
                 .get("data")
                 .and_then(|value| serde_json::from_value(value.clone()).ok())
                 .unwrap();
             black_box(&mut data);
         })
     });
-}
-
-fn deserialize_complex_string(g: &mut BenchmarkGroup<WallTime>) {
-    let data = ComplexData::sample();
-    type Map = HashMap<String, String>;
-    let map: Map = HashMap::from([("data".into(), serde_json::to_string(&data).unwrap())]);
-    let buf = serde_json::to_string(&map).unwrap();
 
-    g.bench_function("string", |b| {
+    g.throughput(bytes(&record.string));
+    g.bench_function(BenchmarkId::new("string", C::NAME), |b| {
         b.iter(|| {
-            let map: Map = serde_json::from_str(black_box(&buf)).unwrap();
-            let mut data: ComplexData = map
-                .get("data")
-                .and_then(|s| serde_json::from_str(s).ok())
-                .unwrap();
+            let map: HashMap<String, C::Nested> = C::decode(black_box(&record.string));
+            let mut data: P = map.get("data").map(C::decode_nested).unwrap();
             black_box(&mut data);
         })
     });
 }
 
-fn get_simple_value(g: &mut BenchmarkGroup<WallTime>) {
-    let data = Data::sample();
-
-    g.bench_function("value", |b| {
+fn serialize_raw<P: Payload>(g: &mut BenchmarkGroup<WallTime>, data: &P) {
+    g.throughput(bytes(&encode_raw_record(data)));
+    g.bench_function(BenchmarkId::new("raw", Json::NAME), |b| {
         b.iter_batched(
-            || serde_json::to_value(&data).unwrap(),
-            |value| {
-                let s = value.get("s").and_then(|s| s.as_str()).unwrap();
-                black_box(s);
+            RawRecord::new,
+            |mut map| {
+                map.insert(
+                    "data".into(),
+                    serde_json::value::to_raw_value(black_box(data)).unwrap(),
+                );
+                black_box(Json::encode(&map));
             },
             BatchSize::SmallInput,
         )
     });
 }
 
-fn get_simple_string(g: &mut BenchmarkGroup<WallTime>) {
-    let data = Data::sample();
+fn deserialize_raw<P: Payload>(g: &mut BenchmarkGroup<WallTime>, data: &P) {
+    let buf = encode_raw_record(data);
 
-    g.bench_function("string", |b| {
+    g.throughput(bytes(&buf));
+    g.bench_function(BenchmarkId::new("raw", Json::NAME), |b| {
+        b.iter(|| {
+            let map: RawRecord = Json::decode(black_box(&buf));
+            let mut data: P = map
+                .get("data")
+                .and_then(|raw| serde_json::from_str(raw.get()).ok())
+                .unwrap();
+            black_box(&mut data);
+        })
+    });
+}
+
+/// The `value` strategy keeps a `serde_json::Value` in memory whatever the
+/// record is encoded with, so it is benchmarked once rather than per codec.
+///
+/// A `Value` has no encoding of its own, so throughput is measured in bytes
+/// of the entry as JSON, whose structure the `Value` mirrors.
+fn get_value<P: Payload>(g: &mut BenchmarkGroup<WallTime>, data: &P) {
+    g.throughput(bytes(&Json::encode(data)));
+    g.bench_function("value", |b| {
         b.iter_batched(
-            || serde_json::to_string(&data).unwrap(),
-            |value| {
-                let data = serde_json::from_str::<Data>(&value).unwrap();
-                black_box(&data.s);
-            },
+            || serde_json::to_value(data).unwrap(),
+            |value| P::read_value(&value),
             BatchSize::SmallInput,
         )
     });
 }
 
-fn get_complex_value(g: &mut BenchmarkGroup<WallTime>) {
-    let data = ComplexData::sample();
-
-    g.bench_function("value", |b| {
+fn get_string<C: Codec, P: Payload>(g: &mut BenchmarkGroup<WallTime>, data: &P) {
+    g.throughput(bytes(C::encode_nested(data).as_ref()));
+    g.bench_function(BenchmarkId::new("string", C::NAME), |b| {
         b.iter_batched(
-            || serde_json::to_value(&data).unwrap(),
-            |value| {
-                let n = value
-                    .get("deeply")
-                    .and_then(|v| v.get("nested"))
-                    .and_then(|v| v.get(3))
-                    .and_then(|v| v.get("value"))
-                    .and_then(|v| v.as_u64())
-                    .unwrap();
-                black_box(n);
+            || C::encode_nested(data),
+            |nested| {
+                let data: P = C::decode_nested(&nested);
+                data.read();
             },
             BatchSize::SmallInput,
         )
     });
 }
 
-fn get_complex_string(g: &mut BenchmarkGroup<WallTime>) {
-    let data = ComplexData::sample();
+fn get_raw<P: Payload>(g: &mut BenchmarkGroup<WallTime>, data: &P) {
+    let raw = serde_json::value::to_raw_value(data).unwrap();
 
-    g.bench_function("string", |b| {
+    g.throughput(bytes(raw.get().as_bytes()));
+    g.bench_function(BenchmarkId::new("raw", Json::NAME), |b| {
         b.iter_batched(
-            || serde_json::to_string(&data).unwrap(),
-            |value| {
-                let data = serde_json::from_str::<ComplexData>(&value).unwrap();
-                black_box(
-                    data.deeply
-                        .get("nested")
-                        .and_then(|v| v.get(3))
-                        .and_then(|m| m.get("value"))
-                        .unwrap(),
-                );
+            || raw.clone(),
+            |raw| {
+                let data: P = serde_json::from_str(raw.get()).unwrap();
+                data.read();
             },
             BatchSize::SmallInput,
         )
     });
 }
 
-fn insert_simple_value(g: &mut BenchmarkGroup<WallTime>) {
-    let data = Data::sample();
-
+/// Like `get_value`, independent of the codec.
+fn insert_value<P: Payload>(g: &mut BenchmarkGroup<WallTime>, data: &P) {
+    g.throughput(bytes(&Json::encode(data)));
     g.bench_function("value_get_mut", |b| {
         b.iter_batched(
-            || serde_json::to_value(&data).unwrap(),
+            || serde_json::to_value(data).unwrap(),
             |mut value| {
-                let s = value.get_mut("s").unwrap();
-                *s = black_box("good night, world!").into();
+                P::write_value(&mut value);
                 black_box(value);
             },
             BatchSize::SmallInput,
@@ -419,10 +680,10 @@ fn insert_simple_value(g: &mut BenchmarkGroup<WallTime>) {
 
     g.bench_function("value_as_data", |b| {
         b.iter_batched(
-            || serde_json::to_value(&data).unwrap(),
+            || serde_json::to_value(data).unwrap(),
             |mut value| {
-                let mut data = serde_json::from_value::<Data>(value).unwrap();
-                data.s = black_box("good night, world!").into();
+                let mut data = serde_json::from_value::<P>(value).unwrap();
+                data.write();
                 value = serde_json::to_value(&data).unwrap();
                 black_box(value);
             },
@@ -431,182 +692,296 @@ fn insert_simple_value(g: &mut BenchmarkGroup<WallTime>) {
     });
 }
 
-fn insert_simple_string(g: &mut BenchmarkGroup<WallTime>) {
-    let data = Data::sample();
-
-    g.bench_function("string", |b| {
+fn insert_string<C: Codec, P: Payload>(g: &mut BenchmarkGroup<WallTime>, data: &P) {
+    g.throughput(bytes(C::encode_nested(data).as_ref()));
+    g.bench_function(BenchmarkId::new("string", C::NAME), |b| {
         b.iter_batched(
-            || serde_json::to_string(&data).unwrap(),
-            |mut s| {
-                let mut v = serde_json::from_str::<Data>(&s).unwrap();
-                v.s = black_box("good night, world!").into();
-                s = serde_json::to_string(&v).unwrap();
-                black_box(s);
+            || C::encode_nested(data),
+            |mut nested| {
+                let mut data: P = C::decode_nested(&nested);
+                data.write();
+                nested = C::encode_nested(&data);
+                black_box(nested);
             },
             BatchSize::SmallInput,
         )
     });
 }
 
-fn insert_complex_value(g: &mut BenchmarkGroup<WallTime>) {
-    let data = ComplexData::sample();
+fn insert_raw<P: Payload>(g: &mut BenchmarkGroup<WallTime>, data: &P) {
+    let raw = serde_json::value::to_raw_value(data).unwrap();
 
-    g.bench_function("value_get_mut", |b| {
+    g.throughput(bytes(raw.get().as_bytes()));
+    g.bench_function(BenchmarkId::new("raw", Json::NAME), |b| {
         b.iter_batched(
-            || serde_json::to_value(&data).unwrap(),
-            |mut value| {
-                let v = value
-                    .get_mut(black_box("deeply"))
-                    .and_then(|v| v.get_mut(black_box("nested")))
-                    .and_then(|v| v.get_mut(black_box(3)))
-                    .and_then(|v| v.get_mut(black_box("value")))
-                    .unwrap();
-                *v = black_box(5).into();
-                black_box(v);
+            || raw.clone(),
+            |mut raw| {
+                let mut data: P = serde_json::from_str(raw.get()).unwrap();
+                data.write();
+                raw = serde_json::value::to_raw_value(&data).unwrap();
+                black_box(raw);
             },
             BatchSize::SmallInput,
         )
     });
+}
 
-    g.bench_function("value_as_data", |b| {
-        b.iter_batched(
-            || serde_json::to_value(&data).unwrap(),
-            |mut value| {
-                let mut data = serde_json::from_value::<ComplexData>(value).unwrap();
-                let v = data
-                    .deeply
-                    .get_mut(black_box("nested"))
-                    .and_then(|v| v.get_mut(black_box(3)))
-                    .and_then(|m| m.get_mut(black_box("value")))
-                    .unwrap();
-                *v = black_box(5);
-                value = serde_json::to_value(&data).unwrap();
-                black_box(value);
-            },
-            BatchSize::SmallInput,
-        )
+/// Decodes a session record and reads the entry holding `P`, leaving the
+/// other entries alone.
+fn get_entry<C: Codec, P: Payload>(g: &mut BenchmarkGroup<WallTime>, size: FixtureSize) {
+    let session = EncodedSession::new::<C>(size);
+
+    g.throughput(bytes(&session.value));
+    g.bench_function(BenchmarkId::new("value", C::NAME), |b| {
+        b.iter(|| {
+            let map: HashMap<String, serde_json::Value> = C::decode(black_box(&session.value));
+            let data = P::deserialize(&map[P::NAME]).unwrap();
+            data.read();
+        })
+    });
+
+    g.throughput(bytes(&session.string));
+    g.bench_function(BenchmarkId::new("string", C::NAME), |b| {
+        b.iter(|| {
+            let map: HashMap<String, C::Nested> = C::decode(black_box(&session.string));
+            let data: P = C::decode_nested(&map[P::NAME]);
+            data.read();
+        })
     });
 }
 
-fn insert_complex_string(g: &mut BenchmarkGroup<WallTime>) {
-    let data = ComplexData::sample();
+fn get_entry_raw<P: Payload>(g: &mut BenchmarkGroup<WallTime>, size: FixtureSize) {
+    let session = EncodedSession::new::<Json>(size);
 
-    g.bench_function("string", |b| {
-        b.iter_batched(
-            || serde_json::to_string(&data).unwrap(),
-            |mut s| {
-                let mut data = serde_json::from_str::<ComplexData>(&s).unwrap();
-                let v = data
-                    .deeply
-                    .get_mut(black_box("nested"))
-                    .and_then(|v| v.get_mut(black_box(3)))
-                    .and_then(|m| m.get_mut(black_box("value")))
-                    .unwrap();
-                *v = black_box(5);
-                s = serde_json::to_string(&data).unwrap();
-                black_box(s);
-            },
-            BatchSize::SmallInput,
-        )
+    g.throughput(bytes(&session.raw));
+    g.bench_function(BenchmarkId::new("raw", Json::NAME), |b| {
+        b.iter(|| {
+            let map: RawRecord = Json::decode(black_box(&session.raw));
+            let data: P = serde_json::from_str(map[P::NAME].get()).unwrap();
+            data.read();
+        })
+    });
+}
+
+/// Decodes a session record, modifies the entry holding `P` and encodes the
+/// record again.
+fn insert_entry<C: Codec, P: Payload>(g: &mut BenchmarkGroup<WallTime>, size: FixtureSize) {
+    let session = EncodedSession::new::<C>(size);
+
+    g.throughput(bytes(&session.value));
+    g.bench_function(BenchmarkId::new("value", C::NAME), |b| {
+        b.iter(|| {
+            let mut map: HashMap<String, serde_json::Value> = C::decode(black_box(&session.value));
+            let value = map.get_mut(P::NAME).unwrap();
+            let mut data = P::deserialize(&*value).unwrap();
+            data.write();
+            *value = serde_json::to_value(&data).unwrap();
+            black_box(C::encode(&map));
+        })
+    });
+
+    g.throughput(bytes(&session.string));
+    g.bench_function(BenchmarkId::new("string", C::NAME), |b| {
+        b.iter(|| {
+            let mut map: HashMap<String, C::Nested> = C::decode(black_box(&session.string));
+            let nested = map.get_mut(P::NAME).unwrap();
+            let mut data: P = C::decode_nested(nested);
+            data.write();
+            *nested = C::encode_nested(&data);
+            black_box(C::encode(&map));
+        })
+    });
+}
+
+fn insert_entry_raw<P: Payload>(g: &mut BenchmarkGroup<WallTime>, size: FixtureSize) {
+    let session = EncodedSession::new::<Json>(size);
+
+    g.throughput(bytes(&session.raw));
+    g.bench_function(BenchmarkId::new("raw", Json::NAME), |b| {
+        b.iter(|| {
+            let mut map: RawRecord = Json::decode(black_box(&session.raw));
+            let raw = map.get_mut(P::NAME).unwrap();
+            let mut data: P = serde_json::from_str(raw.get()).unwrap();
+            data.write();
+            *raw = serde_json::value::to_raw_value(&data).unwrap();
+            black_box(Json::encode(&map));
+        })
     });
 }
 
-fn bench_serialize_simple(c: &mut Criterion) {
-    let mut group = c.benchmark_group("serialize_simple");
-    serialize_simple_direct(&mut group);
-    serialize_simple_value(&mut group);
-    serialize_simple_string(&mut group);
-    group.finish();
-}
-
-fn bench_serialize_big(c: &mut Criterion) {
-    let mut group = c.benchmark_group("serialize_big");
-    serialize_big_direct(&mut group);
-    serialize_big_value(&mut group);
-    serialize_big_string(&mut group);
-    group.finish();
-}
-
-fn bench_serialize_complex(c: &mut Criterion) {
-    let mut group = c.benchmark_group("serialize_complex");
-    serialize_complex_direct(&mut group);
-    serialize_complex_value(&mut group);
-    serialize_complex_string(&mut group);
-    group.finish();
-}
-
-fn bench_deserialize_simple(c: &mut Criterion) {
-    let mut group = c.benchmark_group("deserialize_simple");
-    deserialize_simple_direct(&mut group);
-    deserialize_simple_value(&mut group);
-    deserialize_simple_string(&mut group);
-    group.finish();
-}
-
-fn bench_deserialize_big(c: &mut Criterion) {
-    let mut group = c.benchmark_group("deserialize_big");
-    deserialize_big_direct(&mut group);
-    deserialize_big_value(&mut group);
-    deserialize_big_string(&mut group);
-    group.finish();
-}
-
-fn bench_deserialize_complex(c: &mut Criterion) {
-    let mut group = c.benchmark_group("deserialize_complex");
-    deserialize_complex_direct(&mut group);
-    deserialize_complex_value(&mut group);
-    deserialize_complex_string(&mut group);
-    group.finish();
-}
-
-fn bench_get_simple(c: &mut Criterion) {
-    let mut group = c.benchmark_group("get_simple");
-    get_simple_value(&mut group);
-    get_simple_string(&mut group);
-    group.finish();
-}
-
-fn bench_get_complex(c: &mut Criterion) {
-    let mut group = c.benchmark_group("get_complex");
-    get_complex_value(&mut group);
-    get_complex_string(&mut group);
-    group.finish();
-}
-
-fn bench_insert_simple(c: &mut Criterion) {
-    let mut group = c.benchmark_group("insert_simple");
-    insert_simple_value(&mut group);
-    insert_simple_string(&mut group);
-    group.finish();
-}
-
-fn bench_insert_complex(c: &mut Criterion) {
-    let mut group = c.benchmark_group("insert_complex");
-    insert_complex_value(&mut group);
-    insert_complex_string(&mut group);
-    group.finish();
-}
-
-criterion_group!(serialize_simple, bench_serialize_simple);
-criterion_group!(serialize_big, bench_serialize_big);
-criterion_group!(serialize_complex, bench_serialize_complex);
-criterion_group!(deserialize_simple, bench_deserialize_simple);
-criterion_group!(deserialize_big, bench_deserialize_big);
-criterion_group!(deserialize_complex, bench_deserialize_complex);
-criterion_group!(get_simple, bench_get_simple);
-criterion_group!(get_complex, bench_get_complex);
-criterion_group!(insert_simple, bench_insert_simple);
-criterion_group!(insert_complex, bench_insert_complex);
-
-criterion_main!(
-    serialize_simple,
-    serialize_big,
-    serialize_complex,
-    deserialize_simple,
-    deserialize_big,
-    deserialize_complex,
-    get_simple,
-    get_complex,
-    insert_simple,
-    insert_complex,
+fn bench_serialize<P: Payload>(c: &mut Criterion) {
+    for size in SIZES {
+        let data = fixture::<P>(size);
+        let mut group = c.benchmark_group(format!("serialize_{}_{size}", P::NAME));
+        serialize::<Json, P>(&mut group, &data);
+        serialize::<MsgPack, P>(&mut group, &data);
+        serialize::<MsgPackNamed, P>(&mut group, &data);
+        serialize_raw(&mut group, &data);
+        group.finish();
+    }
+}
+
+fn bench_deserialize<P: Payload>(c: &mut Criterion) {
+    for size in SIZES {
+        let data = fixture::<P>(size);
+        let mut group = c.benchmark_group(format!("deserialize_{}_{size}", P::NAME));
+        deserialize::<Json, P>(&mut group, &data);
+        deserialize::<MsgPack, P>(&mut group, &data);
+        deserialize::<MsgPackNamed, P>(&mut group, &data);
+        deserialize_raw(&mut group, &data);
+        group.finish();
+    }
+}
+
+fn bench_get<P: Payload>(c: &mut Criterion) {
+    for size in SIZES {
+        let data = fixture::<P>(size);
+        let mut group = c.benchmark_group(format!("get_{}_{size}", P::NAME));
+        get_value(&mut group, &data);
+        get_string::<Json, P>(&mut group, &data);
+        get_string::<MsgPack, P>(&mut group, &data);
+        get_string::<MsgPackNamed, P>(&mut group, &data);
+        get_raw(&mut group, &data);
+        group.finish();
+    }
+}
+
+fn bench_insert<P: Payload>(c: &mut Criterion) {
+    for size in SIZES {
+        let data = fixture::<P>(size);
+        let mut group = c.benchmark_group(format!("insert_{}_{size}", P::NAME));
+        insert_value(&mut group, &data);
+        insert_string::<Json, P>(&mut group, &data);
+        insert_string::<MsgPack, P>(&mut group, &data);
+        insert_string::<MsgPackNamed, P>(&mut group, &data);
+        insert_raw(&mut group, &data);
+        group.finish();
+    }
+}
+
+fn bench_get_entry<P: Payload>(c: &mut Criterion) {
+    for size in SIZES {
+        let mut group = c.benchmark_group(format!("get_entry_{}_{size}", P::NAME));
+        get_entry::<Json, P>(&mut group, size);
+        get_entry::<MsgPack, P>(&mut group, size);
+        get_entry::<MsgPackNamed, P>(&mut group, size);
+        get_entry_raw::<P>(&mut group, size);
+        group.finish();
+    }
+}
+
+fn bench_insert_entry<P: Payload>(c: &mut Criterion) {
+    for size in SIZES {
+        let mut group = c.benchmark_group(format!("insert_entry_{}_{size}", P::NAME));
+        insert_entry::<Json, P>(&mut group, size);
+        insert_entry::<MsgPack, P>(&mut group, size);
+        insert_entry::<MsgPackNamed, P>(&mut group, size);
+        insert_entry_raw::<P>(&mut group, size);
+        group.finish();
+    }
+}
+
+criterion_group!(
+    serialize_benches,
+    bench_serialize::<AuthSession>,
+    bench_serialize::<FlashQueue>,
+    bench_serialize::<ShoppingCart>,
+    bench_serialize::<OAuthState>,
+);
+criterion_group!(
+    deserialize_benches,
+    bench_deserialize::<AuthSession>,
+    bench_deserialize::<FlashQueue>,
+    bench_deserialize::<ShoppingCart>,
+    bench_deserialize::<OAuthState>,
+);
+criterion_group!(
+    get_benches,
+    bench_get::<AuthSession>,
+    bench_get::<FlashQueue>,
+    bench_get::<ShoppingCart>,
+    bench_get::<OAuthState>,
 );
+criterion_group!(
+    insert_benches,
+    bench_insert::<AuthSession>,
+    bench_insert::<FlashQueue>,
+    bench_insert::<ShoppingCart>,
+    bench_insert::<OAuthState>,
+);
+criterion_group!(
+    get_entry_benches,
+    bench_get_entry::<AuthSession>,
+    bench_get_entry::<FlashQueue>,
+    bench_get_entry::<ShoppingCart>,
+    bench_get_entry::<OAuthState>,
+);
+criterion_group!(
+    insert_entry_benches,
+    bench_insert_entry::<AuthSession>,
+    bench_insert_entry::<FlashQueue>,
+    bench_insert_entry::<ShoppingCart>,
+    bench_insert_entry::<OAuthState>,
+);
+
+/// Prints the size of every fixture's record for each codec and strategy.
+fn print_size_report() {
+    let codecs = [Json::NAME, MsgPack::NAME, MsgPackNamed::NAME];
+    let rows = [
+        size_rows::<AuthSession>(),
+        size_rows::<FlashQueue>(),
+        size_rows::<ShoppingCart>(),
+        size_rows::<OAuthState>(),
+    ]
+    .concat();
+
+    println!();
+    println!("encoded record size (bytes)");
+    print!("{:14}", "fixture");
+    for codec in codecs {
+        for strategy in ["direct", "value", "string"] {
+            print!("  {:>21}", format!("{strategy}/{codec}"));
+        }
+    }
+    println!("  {:>21}", format!("raw/{}", Json::NAME));
+    for (fixture, sizes) in rows {
+        print!("{fixture:14}");
+        for size in sizes {
+            print!("  {size:>21}");
+        }
+        println!();
+    }
+}
+
+fn size_rows<P: Payload>() -> Vec<(String, Vec<usize>)> {
+    SIZES
+        .into_iter()
+        .map(|size| {
+            let data = fixture::<P>(size);
+            let records = [
+                EncodedRecord::new::<Json, P>(&data),
+                EncodedRecord::new::<MsgPack, P>(&data),
+                EncodedRecord::new::<MsgPackNamed, P>(&data),
+            ];
+            let sizes = records
+                .iter()
+                .flat_map(|record| [&record.direct, &record.value, &record.string])
+                .map(Vec::len)
+                .chain([encode_raw_record(&data).len()])
+                .collect();
+            (format!("{}/{size}", P::NAME), sizes)
+        })
+        .collect()
+}
+
+fn main() {
+    serialize_benches();
+    deserialize_benches();
+    get_benches();
+    insert_benches();
+    get_entry_benches();
+    insert_entry_benches();
+
+    Criterion::default().configure_from_args().final_summary();
+    print_size_report();
+}